@@ -0,0 +1,302 @@
+//! Derive macros re-exported by the `aoc` crate. Use them through `aoc`
+//! rather than depending on this crate directly, as the generated code refers
+//! to `::aoc`.
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, Ident, LitStr, Member, Result,
+};
+
+/// Derive `FromStr` from a format pattern such as
+/// `#[aoc(pattern = "move {mv} from {from} to {to}")]`.
+///
+/// See `aoc::parse` for the full description.
+#[proc_macro_derive(AocParse, attributes(aoc))]
+pub fn derive_aoc_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+/// Split a pattern into literal and `{field}` segments. `{{` and `}}` escape
+/// literal braces.
+fn parse_pattern(pattern: &LitStr) -> Result<Vec<Segment>> {
+    let text = pattern.value();
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => {
+                            return Err(Error::new_spanned(
+                                pattern,
+                                format!("unterminated `{{{name}` in pattern, missing `}}`"),
+                            ))
+                        }
+                    }
+                }
+                let name = name.trim();
+                if name.is_empty() {
+                    return Err(Error::new_spanned(pattern, "empty `{}` in pattern"));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                } else if let Some(Segment::Field(prev)) = segments.last() {
+                    return Err(Error::new_spanned(
+                        pattern,
+                        format!("fields `{prev}` and `{name}` need a literal between them"),
+                    ));
+                }
+                segments.push(Segment::Field(name.to_string()));
+            }
+            '}' => {
+                return Err(Error::new_spanned(
+                    pattern,
+                    "unmatched `}` in pattern, use `}}` for a literal brace",
+                ))
+            }
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+/// Find the single `#[aoc(pattern = "...")]` among `attrs`.
+fn find_pattern(attrs: &[Attribute], span: Span) -> Result<LitStr> {
+    let mut pattern = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("aoc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("pattern") {
+                if pattern.is_some() {
+                    return Err(meta.error("duplicate `pattern`"));
+                }
+                pattern = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `pattern = \"...\"`"))
+            }
+        })?;
+    }
+    pattern.ok_or_else(|| Error::new(span, "missing `#[aoc(pattern = \"...\")]`"))
+}
+
+/// Build the statements that parse `fields` according to `pattern`, ending
+/// in an expression that constructs `path`.
+fn parse_body(path: &TokenStream2, fields: &Fields, pattern: &LitStr) -> Result<TokenStream2> {
+    let segments = parse_pattern(pattern)?;
+
+    let members: Vec<Member> = match fields {
+        Fields::Named(named) => named
+            .named
+            .iter()
+            .map(|f| Member::Named(f.ident.clone().expect("named field")))
+            .collect(),
+        Fields::Unnamed(unnamed) => (0..unnamed.unnamed.len()).map(Member::from).collect(),
+        Fields::Unit => Vec::new(),
+    };
+    let member_name = |member: &Member| match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    };
+
+    let mut seen = Vec::new();
+    for segment in &segments {
+        if let Segment::Field(name) = segment {
+            if !members.iter().any(|m| member_name(m) == *name) {
+                return Err(Error::new_spanned(
+                    pattern,
+                    format!("pattern refers to unknown field `{name}`"),
+                ));
+            }
+            if seen.contains(&name) {
+                return Err(Error::new_spanned(
+                    pattern,
+                    format!("field `{name}` appears more than once in pattern"),
+                ));
+            }
+            seen.push(name);
+        }
+    }
+    if let Some(missing) = members.iter().find(|m| !seen.contains(&&member_name(m))) {
+        return Err(Error::new_spanned(
+            pattern,
+            format!("field `{}` is missing from pattern", member_name(missing)),
+        ));
+    }
+
+    let local = |name: &str| format_ident!("__field_{}", name);
+    let mut steps = Vec::new();
+    for (idx, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(lit) => steps.push(quote! { __cursor.literal(#lit)?; }),
+            Segment::Field(name) => {
+                let var = local(name);
+                let until = match segments.get(idx + 1) {
+                    Some(Segment::Literal(lit)) => quote! { ::std::option::Option::Some(#lit) },
+                    _ => quote! { ::std::option::Option::None },
+                };
+                steps.push(quote! { let #var = __cursor.field(#name, #until)?; });
+            }
+        }
+    }
+
+    let construct = match fields {
+        Fields::Named(_) => {
+            let inits = members.iter().map(|m| {
+                let var = local(&member_name(m));
+                quote! { #m: #var }
+            });
+            quote! { #path { #(#inits),* } }
+        }
+        Fields::Unnamed(_) => {
+            let vars = members.iter().map(|m| local(&member_name(m)));
+            quote! { #path ( #(#vars),* ) }
+        }
+        Fields::Unit => quote! { #path },
+    };
+
+    Ok(quote! {
+        #(#steps)*
+        __cursor.finish()?;
+        ::std::result::Result::Ok(#construct)
+    })
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let pattern = find_pattern(&input.attrs, ident.span())?;
+            let body = parse_body(&quote!(Self), &data.fields, &pattern)?;
+            quote! {
+                let mut __cursor = ::aoc::parse::Cursor::new(#name, s);
+                let __parsed: ::std::result::Result<Self, ::aoc::parse::ParseError> = (|| { #body })();
+                __parsed.map_err(::std::convert::Into::into)
+            }
+        }
+        Data::Enum(data) => {
+            if data.variants.is_empty() {
+                return Err(Error::new_spanned(
+                    ident,
+                    "cannot derive AocParse for an empty enum",
+                ));
+            }
+            let mut attempts = Vec::new();
+            let mut patterns = Vec::new();
+            for variant in &data.variants {
+                let pattern = find_pattern(&variant.attrs, variant.ident.span())?;
+                let vident: &Ident = &variant.ident;
+                let vname = format!("{name}::{vident}");
+                let body = parse_body(&quote!(Self::#vident), &variant.fields, &pattern)?;
+                attempts.push(quote! {
+                    {
+                        let mut __cursor = ::aoc::parse::Cursor::new(#vname, s);
+                        let __attempt: ::std::result::Result<Self, ::aoc::parse::ParseError> =
+                            (|| { #body })();
+                        match __attempt {
+                            ::std::result::Result::Ok(value) => {
+                                return ::std::result::Result::Ok(value)
+                            }
+                            ::std::result::Result::Err(e) => __errors.push(e),
+                        }
+                    }
+                });
+                patterns.push(pattern);
+            }
+            quote! {
+                let mut __errors = ::std::vec::Vec::new();
+                #(#attempts)*
+                ::std::result::Result::Err(
+                    ::aoc::parse::ParseError::best_of(#name, s, &[#(#patterns),*], __errors).into()
+                )
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                ident,
+                "cannot derive AocParse for a union",
+            ));
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #ident #ty_generics #where_clause {
+            type Err = ::aoc::Error;
+
+            #[allow(clippy::redundant_closure_call)]
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                #body
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    use super::*;
+
+    fn parse(pattern: &str) -> Result<Vec<Segment>> {
+        parse_pattern(&LitStr::new(pattern, Span::call_site()))
+    }
+
+    #[test]
+    fn test_parse_pattern() {
+        assert_eq!(
+            parse("move {n} from {{x}}").unwrap(),
+            [
+                Segment::Literal("move ".into()),
+                Segment::Field("n".into()),
+                Segment::Literal(" from {x}".into()),
+            ]
+        );
+        assert_eq!(parse("{ 0 }").unwrap(), [Segment::Field("0".into())]);
+    }
+
+    #[test]
+    fn test_parse_pattern_errors() {
+        for (pattern, message) in [
+            ("move {n", "unterminated `{n` in pattern, missing `}`"),
+            ("{", "unterminated `{` in pattern, missing `}`"),
+            ("a {} b", "empty `{}` in pattern"),
+            ("{a}{b}", "fields `a` and `b` need a literal between them"),
+            (
+                "a } b",
+                "unmatched `}` in pattern, use `}}` for a literal brace",
+            ),
+        ] {
+            let err = parse(pattern).map(|_| ()).unwrap_err();
+            assert_eq!(err.to_string(), message, "{pattern:?}");
+        }
+    }
+}
@@ -0,0 +1,416 @@
+//! Fixed-size sets over small alphabets, stored as the bits of a single
+//! integer so that set operations never allocate.
+//!
+//! ```rust
+//! use aoc::bitset::{Alphabet, BitSet, Letters};
+//!
+//! let first: BitSet<Letters> = "vJrwpWtwJgWr".chars().collect();
+//! let second: BitSet<Letters> = "hcsFMMfFFhFp".chars().collect();
+//! let common = first & second;
+//! assert_eq!(common.iter().collect::<String>(), "p");
+//! assert_eq!(common.first().and_then(Letters::index), Some(15));
+//! ```
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not, Shl, Sub},
+};
+
+use crate::{err, Result};
+
+/// Maps the symbols of an alphabet to bit indices `0..SIZE`.
+pub trait Alphabet {
+    const SIZE: u32;
+
+    fn index(symbol: char) -> Option<u32>;
+
+    fn symbol(index: u32) -> Option<char>;
+}
+
+/// Implement [`Alphabet`] for a list of contiguous `char` ranges, numbered
+/// consecutively in the order given.
+macro_rules! alphabet {
+    ($(#[$meta:meta])* $name:ident: $($start:literal..=$end:literal),+) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        pub struct $name;
+
+        impl Alphabet for $name {
+            const SIZE: u32 = 0 $(+ ($end as u32 - $start as u32 + 1))+;
+
+            #[allow(clippy::manual_is_ascii_check)]
+            fn index(symbol: char) -> Option<u32> {
+                let mut offset = 0;
+                $(
+                    if ($start..=$end).contains(&symbol) {
+                        return Some(offset + (symbol as u32 - $start as u32));
+                    }
+                    offset += $end as u32 - $start as u32 + 1;
+                )+
+                let _ = offset;
+                None
+            }
+
+            fn symbol(index: u32) -> Option<char> {
+                let mut index = index;
+                $(
+                    let len = $end as u32 - $start as u32 + 1;
+                    if index < len {
+                        return char::from_u32($start as u32 + index);
+                    }
+                    index -= len;
+                )+
+                let _ = index;
+                None
+            }
+        }
+    };
+}
+
+alphabet!(
+    /// `a..=z`
+    Lower: 'a'..='z'
+);
+alphabet!(
+    /// `A..=Z`
+    Upper: 'A'..='Z'
+);
+alphabet!(
+    /// `a..=z` followed by `A..=Z`, so that the index is one less than the
+    /// day 3 item priority.
+    Letters: 'a'..='z', 'A'..='Z'
+);
+alphabet!(
+    /// `0..=9`
+    Digits: '0'..='9'
+);
+alphabet!(
+    /// All of ASCII; needs a `u128` word.
+    Ascii: '\0'..='\x7f'
+);
+
+/// Unsigned integers that can back a [`BitSet`].
+pub trait Word:
+    Copy
+    + Eq
+    + Hash
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + Not<Output = Self>
+    + Shl<u32, Output = Self>
+{
+    const BITS: u32;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn count_ones(self) -> u32;
+
+    fn trailing_zeros(self) -> u32;
+}
+
+macro_rules! impl_word {
+    ($($ty:ty),*) => {
+        $(
+            impl Word for $ty {
+                const BITS: u32 = <$ty>::BITS;
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn count_ones(self) -> u32 {
+                    <$ty>::count_ones(self)
+                }
+
+                fn trailing_zeros(self) -> u32 {
+                    <$ty>::trailing_zeros(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_word!(u32, u64, u128);
+
+/// A set of symbols from alphabet `A`, one bit of `W` per symbol.
+pub struct BitSet<A, W = u64> {
+    bits: W,
+    alphabet: PhantomData<A>,
+}
+
+impl<A: Alphabet, W: Word> BitSet<A, W> {
+    /// Fails to compile if the alphabet doesn't fit in the word.
+    const FITS: () = assert!(A::SIZE <= W::BITS, "alphabet is too large for word size");
+
+    #[must_use]
+    pub fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::FITS;
+        Self {
+            bits: W::ZERO,
+            alphabet: PhantomData,
+        }
+    }
+
+    fn bit(symbol: char) -> Result<W> {
+        A::index(symbol)
+            .map(|idx| W::ONE << idx)
+            .ok_or_else(|| err!("{:?} is not in the alphabet", symbol))
+    }
+
+    /// Build a set from symbols, failing on the first one outside the
+    /// alphabet. [`FromIterator`] is the panicking equivalent.
+    pub fn try_from_iter<I>(iter: I) -> Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<char>,
+    {
+        let mut set = Self::new();
+        for symbol in iter {
+            set.insert(symbol.into())?;
+        }
+        Ok(set)
+    }
+
+    /// Add `symbol`, returning whether it was newly inserted.
+    pub fn insert(&mut self, symbol: char) -> Result<bool> {
+        let bit = Self::bit(symbol)?;
+        let new = self.bits & bit == W::ZERO;
+        self.bits = self.bits | bit;
+        Ok(new)
+    }
+
+    /// Remove `symbol`, returning whether it was present.
+    pub fn remove(&mut self, symbol: char) -> bool {
+        let Ok(bit) = Self::bit(symbol) else {
+            return false;
+        };
+        let present = self.bits & bit != W::ZERO;
+        self.bits = self.bits & !bit;
+        present
+    }
+
+    #[must_use]
+    pub fn contains(&self, symbol: char) -> bool {
+        Self::bit(symbol).is_ok_and(|bit| self.bits & bit != W::ZERO)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bits == W::ZERO
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        *self & *other
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        *self | *other
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        *self - *other
+    }
+
+    #[must_use]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.bits & other.bits == self.bits
+    }
+
+    /// The symbol with the lowest index.
+    #[must_use]
+    pub fn first(&self) -> Option<char> {
+        self.indices().next().and_then(A::symbol)
+    }
+
+    /// The bit indices of the members, in increasing order.
+    pub fn indices(&self) -> impl Iterator<Item = u32> {
+        let mut bits = self.bits;
+        std::iter::from_fn(move || {
+            if bits == W::ZERO {
+                return None;
+            }
+            let idx = bits.trailing_zeros();
+            bits = bits & !(W::ONE << idx);
+            Some(idx)
+        })
+    }
+
+    /// The members, in alphabet order.
+    pub fn iter(&self) -> impl Iterator<Item = char> {
+        self.indices().filter_map(A::symbol)
+    }
+}
+
+impl<A: Alphabet, W: Word> Default for BitSet<A, W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Manual impls so that the marker `A` needn't implement these itself
+impl<A, W: Copy> Clone for BitSet<A, W> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, W: Copy> Copy for BitSet<A, W> {}
+
+impl<A, W: PartialEq> PartialEq for BitSet<A, W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<A, W: Eq> Eq for BitSet<A, W> {}
+
+impl<A, W: Hash> Hash for BitSet<A, W> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits.hash(state);
+    }
+}
+
+impl<A: Alphabet, W: Word> fmt::Debug for BitSet<A, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<A: Alphabet, W: Word> BitAnd for BitSet<A, W> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self {
+            bits: self.bits & rhs.bits,
+            alphabet: PhantomData,
+        }
+    }
+}
+
+impl<A: Alphabet, W: Word> BitAndAssign for BitSet<A, W> {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = *self & rhs;
+    }
+}
+
+impl<A: Alphabet, W: Word> BitOr for BitSet<A, W> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self {
+            bits: self.bits | rhs.bits,
+            alphabet: PhantomData,
+        }
+    }
+}
+
+impl<A: Alphabet, W: Word> BitOrAssign for BitSet<A, W> {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+impl<A: Alphabet, W: Word> Sub for BitSet<A, W> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            bits: self.bits & !rhs.bits,
+            alphabet: PhantomData,
+        }
+    }
+}
+
+/// # Panics
+///
+/// Panics on a symbol outside the alphabet; see [`BitSet::try_from_iter`].
+impl<A: Alphabet, W: Word> FromIterator<char> for BitSet<A, W> {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        Self::try_from_iter(iter).unwrap()
+    }
+}
+
+/// # Panics
+///
+/// Panics on a byte outside the alphabet; see [`BitSet::try_from_iter`].
+impl<A: Alphabet, W: Word> FromIterator<u8> for BitSet<A, W> {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        Self::try_from_iter(iter).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alphabets() {
+        assert_eq!(Lower::SIZE, 26);
+        assert_eq!(Letters::SIZE, 52);
+        assert_eq!(Ascii::SIZE, 128);
+
+        assert_eq!(Letters::index('a'), Some(0));
+        assert_eq!(Letters::index('z'), Some(25));
+        assert_eq!(Letters::index('A'), Some(26));
+        assert_eq!(Letters::index('Z'), Some(51));
+        assert_eq!(Letters::index('0'), None);
+        assert_eq!(Letters::symbol(26), Some('A'));
+        assert_eq!(Letters::symbol(52), None);
+        assert_eq!(Digits::index('7'), Some(7));
+        assert_eq!(Upper::symbol(2), Some('C'));
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = BitSet::<Lower>::new();
+        assert!(set.is_empty());
+        assert!(set.insert('q').unwrap());
+        assert!(!set.insert('q').unwrap());
+        assert!(set.insert('A').is_err());
+        assert!(set.contains('q') && !set.contains('r') && !set.contains('A'));
+        assert_eq!(set.len(), 1);
+        assert!(set.remove('q'));
+        assert!(!set.remove('q'));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: BitSet<Lower> = "abcd".chars().collect();
+        let b: BitSet<Lower> = b"cdef".iter().copied().collect();
+        assert_eq!(a.intersection(&b).iter().collect::<String>(), "cd");
+        assert_eq!(a.union(&b).len(), 6);
+        assert_eq!((a - b).iter().collect::<String>(), "ab");
+        assert!((a & b).is_subset(&a));
+        assert!(!a.is_subset(&b));
+        assert_eq!(a.first(), Some('a'));
+        assert_eq!(format!("{:?}", a & b), "{'c', 'd'}");
+    }
+
+    #[test]
+    fn test_try_from_iter() {
+        assert!(BitSet::<Digits>::try_from_iter("123".chars()).is_ok());
+        assert!(BitSet::<Digits>::try_from_iter("12a".chars()).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "not in the alphabet")]
+    fn test_from_iter_panics() {
+        let _: BitSet<Upper> = "abc".chars().collect();
+    }
+
+    #[test]
+    fn test_wide_word() {
+        let set: BitSet<Ascii, u128> = "~ aZ\x7f".chars().collect();
+        assert_eq!(set.len(), 5);
+        assert_eq!(set.iter().collect::<String>(), " Za~\x7f");
+    }
+}
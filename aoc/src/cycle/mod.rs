@@ -0,0 +1,338 @@
+//! Detect a repeating state in a simulation, then extrapolate to a step far
+//! beyond what could be simulated directly.
+//!
+//! [`floyd`] and [`brent`] compare whole states from any cloneable iterator.
+//! [`Detector`] is for step-by-step loops where only part of the state
+//! determines the future (like the top rows of a tower), keyed by a hashable
+//! projection, and records a running metric for [`Cycle::fast_forward`]:
+//!
+//! ```rust
+//! use aoc::cycle::Detector;
+//!
+//! // A "tower" that grows by 1, 3, 2, 3, 2, 3, 2, ... per step
+//! let growth = |step: usize| if step == 0 { 1 } else { [3, 2][(step - 1) % 2] };
+//!
+//! let mut detector = Detector::new();
+//! let mut height = 0_u64;
+//! let mut step = 0;
+//! let cycle = loop {
+//!     // The state that determines the future is just the phase
+//!     let key = if step == 0 { 0 } else { 1 + (step - 1) % 2 };
+//!     if let Some(cycle) = detector.observe(key, height) {
+//!         break cycle;
+//!     }
+//!     height += growth(step);
+//!     step += 1;
+//! };
+//! assert_eq!((cycle.start, cycle.period), (1, 2));
+//!
+//! let brute_force: u64 = (0..1_000).map(growth).sum();
+//! assert_eq!(detector.fast_forward(&cycle, 1_000).unwrap(), brute_force);
+//! assert_eq!(detector.fast_forward(&cycle, 1_000_000_000_000).unwrap(), 2_500_000_000_000 - 1);
+//! ```
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{Add, Mul, Sub},
+};
+
+use crate::{err, Result};
+
+/// A repeating run of states: the state at every step `n >= start` equals the
+/// state at step `n + period`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as step `n`.
+    #[must_use]
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+
+    /// Extrapolate an accumulated metric to step `n`, where `history[i]` is
+    /// the metric at step `i` and the metric grows by the same amount over
+    /// each period. `history` must reach at least step `start + period`.
+    pub fn fast_forward<T>(&self, history: &[T], n: usize) -> Result<T>
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + TryFrom<usize>,
+    {
+        if let Some(&value) = history.get(n) {
+            return Ok(value);
+        }
+        let (Some(&first), Some(&second)) = (
+            history.get(self.start),
+            history.get(self.start + self.period),
+        ) else {
+            return Err(err!(
+                "history of {} steps is too short for cycle {:?}",
+                history.len(),
+                self
+            ));
+        };
+        let per_period = second - first;
+        let periods = T::try_from((n - self.start) / self.period)
+            .map_err(|_| err!("number of periods to step {} does not fit the metric", n))?;
+        Ok(history[self.equivalent_step(n)] + periods * per_period)
+    }
+}
+
+/// Floyd's tortoise-and-hare cycle detection over a sequence of states.
+/// Returns `None` if the iterator ends before a state repeats.
+pub fn floyd<I>(states: I) -> Option<Cycle>
+where
+    I: Iterator + Clone,
+    I::Item: PartialEq,
+{
+    // Find some i where state[i] == state[2i]
+    let mut tortoise = states.clone();
+    let mut hare = states.clone();
+    tortoise.next()?;
+    hare.next()?;
+    let mut hare_state = loop {
+        let tortoise_state = tortoise.next()?;
+        hare.next()?;
+        let hare_state = hare.next()?;
+        if tortoise_state == hare_state {
+            break hare_state;
+        }
+    };
+
+    // Walking from the beginning and from 2i in step meet at the cycle start
+    let mut tortoise = states;
+    let mut tortoise_state = tortoise.next()?;
+    let mut start = 0;
+    while tortoise_state != hare_state {
+        tortoise_state = tortoise.next()?;
+        hare_state = hare.next()?;
+        start += 1;
+    }
+
+    let mut period = 1;
+    while hare.next()? != tortoise_state {
+        period += 1;
+    }
+    Some(Cycle { start, period })
+}
+
+/// Brent's cycle detection, which usually needs fewer state comparisons than
+/// [`floyd`]. Returns `None` if the iterator ends before a state repeats.
+pub fn brent<I>(states: I) -> Option<Cycle>
+where
+    I: Iterator + Clone,
+    I::Item: PartialEq,
+{
+    // Find the period by comparing against checkpoints at powers of two
+    let mut hare = states.clone();
+    let mut checkpoint = hare.next()?;
+    let mut hare_state = hare.next()?;
+    let (mut power, mut period) = (1, 1);
+    while checkpoint != hare_state {
+        if power == period {
+            checkpoint = hare_state;
+            power *= 2;
+            period = 0;
+        }
+        hare_state = hare.next()?;
+        period += 1;
+    }
+
+    // Two walkers `period` apart meet at the cycle start
+    let mut tortoise = states.clone();
+    let mut hare = states;
+    let mut tortoise_state = tortoise.next()?;
+    let mut hare_state = hare.nth(period)?;
+    let mut start = 0;
+    while tortoise_state != hare_state {
+        tortoise_state = tortoise.next()?;
+        hare_state = hare.next()?;
+        start += 1;
+    }
+    Some(Cycle { start, period })
+}
+
+/// Detect a cycle by remembering a key for every state. Unlike [`floyd`] and
+/// [`brent`] this needs memory for each step, but the iterator needn't be
+/// cloneable and the key can be a projection of the state.
+pub fn find_by_key<I, K, F>(states: I, mut key: F) -> Option<Cycle>
+where
+    I: IntoIterator,
+    K: Hash + Eq,
+    F: FnMut(&I::Item) -> K,
+{
+    let mut detector = Detector::new();
+    states
+        .into_iter()
+        .find_map(|state| detector.observe(key(&state), ()))
+}
+
+/// Records a key and a metric for each step of a simulation loop, reporting a
+/// [`Cycle`] as soon as a key repeats.
+#[derive(Debug, Clone)]
+pub struct Detector<K, V = ()> {
+    seen: HashMap<K, usize>,
+    history: Vec<V>,
+}
+
+impl<K: Hash + Eq, V> Detector<K, V> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            seen: HashMap::new(),
+            history: Vec::new(),
+        }
+    }
+
+    /// Record the key and metric for the next step (starting at step 0),
+    /// returning the cycle if this key was seen at an earlier step.
+    pub fn observe(&mut self, key: K, metric: V) -> Option<Cycle> {
+        let step = self.history.len();
+        self.history.push(metric);
+        self.seen.insert(key, step).map(|start| Cycle {
+            start,
+            period: step - start,
+        })
+    }
+
+    /// The metric recorded at each step so far.
+    pub fn history(&self) -> &[V] {
+        &self.history
+    }
+
+    /// The number of steps observed.
+    pub fn steps(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Extrapolate the recorded metric to step `n`; see
+    /// [`Cycle::fast_forward`].
+    pub fn fast_forward(&self, cycle: &Cycle, n: usize) -> Result<V>
+    where
+        V: Copy + Add<Output = V> + Sub<Output = V> + Mul<Output = V> + TryFrom<usize>,
+    {
+        cycle.fast_forward(&self.history, n)
+    }
+}
+
+impl<K: Hash + Eq, V> Default for Detector<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An iterator over `x -> (x * x + 1) % modulus`.
+    fn sequence(seed: u32, modulus: u32) -> impl Iterator<Item = u32> + Clone {
+        std::iter::successors(Some(seed), move |x| Some((x * x + 1) % modulus))
+    }
+
+    /// Brute force: the first repeated state and its distance back.
+    fn naive(seed: u32, modulus: u32) -> Cycle {
+        let mut seen = Vec::new();
+        for state in sequence(seed, modulus) {
+            if let Some(start) = seen.iter().position(|&s| s == state) {
+                return Cycle {
+                    start,
+                    period: seen.len() - start,
+                };
+            }
+            seen.push(state);
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn test_detectors_agree() {
+        for modulus in [7, 255, 1_000, 4_099] {
+            for seed in 0..20 {
+                let expected = naive(seed, modulus);
+                assert_eq!(floyd(sequence(seed, modulus)), Some(expected));
+                assert_eq!(brent(sequence(seed, modulus)), Some(expected));
+                assert_eq!(find_by_key(sequence(seed, modulus), |&x| x), Some(expected));
+            }
+        }
+    }
+
+    #[test]
+    fn test_pure_cycle() {
+        let cycle = Cycle {
+            start: 0,
+            period: 3,
+        };
+        let states = [1, 2, 3].into_iter().cycle();
+        assert_eq!(floyd(states.clone()), Some(cycle));
+        assert_eq!(brent(states), Some(cycle));
+    }
+
+    #[test]
+    fn test_no_cycle() {
+        assert_eq!(floyd(0..100), None);
+        assert_eq!(brent(0..100), None);
+        assert_eq!(find_by_key(0..100, |&x| x), None);
+        assert_eq!(floyd(std::iter::empty::<u8>()), None);
+    }
+
+    #[test]
+    fn test_projected_key() {
+        // Positions on a 5-cell ring advance by 2 each step; only the
+        // position matters, not the lap count
+        let states = (0..).map(|step: usize| (step * 2 % 5, step * 2 / 5));
+        assert_eq!(
+            find_by_key(states, |&(position, _)| position),
+            Some(Cycle {
+                start: 0,
+                period: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_equivalent_step() {
+        let cycle = Cycle {
+            start: 3,
+            period: 4,
+        };
+        assert_eq!(cycle.equivalent_step(2), 2);
+        assert_eq!(cycle.equivalent_step(3), 3);
+        assert_eq!(cycle.equivalent_step(7), 3);
+        assert_eq!(cycle.equivalent_step(12), 4);
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        // Metric grows by 1 for 3 steps, then +5 +0 +2 forever
+        let deltas = |step: usize| {
+            if step < 3 {
+                1
+            } else {
+                [5, 0, 2][(step - 3) % 3]
+            }
+        };
+        let history: Vec<i64> = (0..=6)
+            .scan(0, |acc, step| {
+                let value = *acc;
+                *acc += deltas(step);
+                Some(value)
+            })
+            .collect();
+        let cycle = Cycle {
+            start: 3,
+            period: 3,
+        };
+        for n in [0, 2, 6, 7, 100, 1_001] {
+            let expected: i64 = (0..n).map(deltas).sum();
+            assert_eq!(cycle.fast_forward(&history, n).unwrap(), expected);
+        }
+        assert!(cycle.fast_forward(&history[..5], 100).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+//! Errors that point at the part of the puzzle input they came from, and
+//! render like a compiler diagnostic:
+//!
+//! ```text
+//! error: ElfPair: field `1` could not parse "6-x" at column 5: invalid digit found in string
+//!  --> y2022/d04/input.txt:3:5
+//!   |
+//! 3 | 5-7,6-x
+//!   |     ^^^
+//! ```
+//!
+//! A parser that works on slices of its input attaches the span of the slice
+//! that failed with [`DiagnosticExt::at`]. An [`AocParse`](crate::AocParse)
+//! error narrows the span to the token that failed within it:
+//!
+//! ```rust
+//! use aoc::{diagnostic::{Diagnostic, DiagnosticExt}, AocParse, Result};
+//!
+//! #[derive(AocParse, Debug)]
+//! #[aoc(pattern = "{0}-{1}")]
+//! struct Range(u32, u32);
+//!
+//! fn parse(input: &str) -> Result<Vec<Range>> {
+//!     input.lines().map(|line| line.parse().at(input, line)).collect()
+//! }
+//!
+//! let err = parse("1-2\n3-four").unwrap_err();
+//! let diagnostic = err.downcast_ref::<Diagnostic>().unwrap();
+//! assert_eq!((diagnostic.line, diagnostic.column), (2, 3));
+//! assert_eq!(diagnostic.render("input.txt").lines().last(), Some("  |   ^^^^"));
+//! ```
+use std::{error, fmt, ops::Range, result};
+
+use crate::{parse::ParseError, Error, Result};
+
+/// An error at a span of the puzzle input.
+#[derive(Debug)]
+pub struct Diagnostic {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, in characters, where the span starts.
+    pub column: usize,
+    /// The text of the line, without its line ending.
+    pub text: String,
+    /// How many characters of the line the span covers, at least 1.
+    pub width: usize,
+    pub error: Error,
+}
+
+impl Diagnostic {
+    /// An error at the byte range `span` of `input`. `input` must start at
+    /// the beginning of the puzzle input for the line numbers to be right.
+    /// A span that runs past the end of its line is cut off there.
+    #[must_use]
+    pub fn new(input: &str, span: Range<usize>, error: impl Into<Error>) -> Self {
+        let start = floor_char_boundary(input, span.start.min(input.len()));
+        let line_start = input[..start].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = input[start..]
+            .find('\n')
+            .map_or(input.len(), |idx| start + idx);
+        let end = floor_char_boundary(input, span.end.clamp(start, line_end));
+        Self {
+            line: input[..start].matches('\n').count() + 1,
+            column: input[line_start..start].chars().count() + 1,
+            text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            width: input[start..end].chars().count().max(1),
+            error: error.into(),
+        }
+    }
+
+    /// An error at `token`, which must be a slice of `input` (as returned by
+    /// `lines`, `split` and the like), or `None` if it isn't.
+    #[must_use]
+    pub fn at(input: &str, token: &str, error: impl Into<Error>) -> Option<Self> {
+        let start = offset_of(input, token)?;
+        Some(Self::at_offset(input, start, token, error.into()))
+    }
+
+    fn at_offset(input: &str, start: usize, token: &str, error: Error) -> Self {
+        let span = match error.downcast_ref::<ParseError>() {
+            Some(e) if e.line == token => start + e.offset..start + e.offset + e.len,
+            _ => start..start + token.len(),
+        };
+        Self::new(input, span, error)
+    }
+
+    /// The diagnostic as a compiler would show it, naming the input `path`.
+    #[must_use]
+    pub fn render(&self, path: &str) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        format!(
+            "error: {}\n{gutter}--> {path}:{}:{}\n{gutter} |\n{} | {}\n{gutter} | {}{}",
+            self.error,
+            self.line,
+            self.column,
+            self.line,
+            self.text,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width),
+        )
+    }
+}
+
+fn floor_char_boundary(s: &str, mut idx: usize) -> usize {
+    while !s.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
+
+/// Where `token` starts within `input`, if it's a slice of it.
+fn offset_of(input: &str, token: &str) -> Option<usize> {
+    let start = (token.as_ptr() as usize).checked_sub(input.as_ptr() as usize)?;
+    (start + token.len() <= input.len()).then_some(start)
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.error
+        )
+    }
+}
+
+impl error::Error for Diagnostic {}
+
+/// Attach the span an error came from.
+pub trait DiagnosticExt<T> {
+    /// Turn an error into a [`Diagnostic`] at `token`, a slice of `input`.
+    /// An error that already is a `Diagnostic`, or a `token` outside of
+    /// `input`, leaves the error as it was.
+    fn at(self, input: &str, token: &str) -> Result<T>;
+}
+
+impl<T, E: Into<Error>> DiagnosticExt<T> for result::Result<T, E> {
+    fn at(self, input: &str, token: &str) -> Result<T> {
+        self.map_err(|e| {
+            let e = e.into();
+            match offset_of(input, token) {
+                Some(start) if !e.is::<Diagnostic>() => {
+                    Diagnostic::at_offset(input, start, token, e).into()
+                }
+                _ => e,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::err;
+
+    const INPUT: &str = "move 1 from 2 to 3\nmove 2 from x to 1\n";
+
+    #[test]
+    fn test_new() {
+        let d = Diagnostic::new(INPUT, 31..32, err!("bad stack"));
+        assert_eq!((d.line, d.column, d.width), (2, 13, 1));
+        assert_eq!(d.text, "move 2 from x to 1");
+        assert_eq!(d.to_string(), "line 2, column 13: bad stack");
+    }
+
+    #[test]
+    fn test_span_stops_at_end_of_line() {
+        let d = Diagnostic::new(INPUT, 12..100, err!("oops"));
+        assert_eq!((d.line, d.column, d.width), (1, 13, 6));
+        let d = Diagnostic::new("ab\r\ncd", 1..4, err!("oops"));
+        assert_eq!((d.text.as_str(), d.width), ("ab", 2));
+    }
+
+    #[test]
+    fn test_at_line() {
+        let line = INPUT.lines().nth(1).unwrap();
+        let d = Diagnostic::at(INPUT, line, err!("no such stack")).unwrap();
+        assert_eq!((d.line, d.column, d.width), (2, 1, 18));
+        assert!(Diagnostic::at(INPUT, &String::from("move 2"), err!("copy")).is_none());
+    }
+
+    #[test]
+    fn test_at_narrows_parse_errors() {
+        #[allow(dead_code)]
+        #[derive(crate::AocParse, Debug)]
+        #[aoc(pattern = "move {0} from {1} to {2}")]
+        struct Move(u8, u8, u8);
+
+        let line = INPUT.lines().nth(1).unwrap();
+        let err = line.parse::<Move>().at(INPUT, line).unwrap_err();
+        let d = err.downcast_ref::<Diagnostic>().unwrap();
+        assert_eq!((d.line, d.column, d.width), (2, 13, 1));
+        assert_eq!(
+            d.render("y2022/d05/input.txt"),
+            [
+                r#"error: Move: field `1` could not parse "x" at column 13: invalid digit found in string"#,
+                " --> y2022/d05/input.txt:2:13",
+                "  |",
+                "2 | move 2 from x to 1",
+                "  |             ^",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_at_keeps_existing_diagnostic() {
+        let first = INPUT.lines().next().unwrap();
+        let second = INPUT.lines().nth(1).unwrap();
+        let err: Result<()> = Err(Diagnostic::at(INPUT, second, err!("inner")).unwrap().into());
+        let err = err.at(INPUT, first).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: inner");
+    }
+
+    #[test]
+    fn test_render_wide_gutter() {
+        let input = "x\n".repeat(11);
+        let d = Diagnostic::new(&input, 20..21, err!("eleventh"));
+        assert_eq!(
+            d.render("input.txt"),
+            "error: eleventh\n  --> input.txt:11:1\n   |\n11 | x\n   | ^"
+        );
+    }
+}
@@ -0,0 +1,622 @@
+//! Two-dimensional grids: [`Grid`], a dense rectangle such as a map read
+//! from the puzzle input, and [`SparseGrid`], a hash map of occupied points
+//! on an unbounded plane that keeps track of the box around them.
+//!
+//! `y` grows downwards, as in the puzzle text, so [`Point::UP`] is `(0, -1)`.
+//!
+//! ```rust
+//! use aoc::grid::{Point, SparseGrid};
+//!
+//! let mut rope = SparseGrid::new();
+//! let mut head = Point::new(0, 0);
+//! for step in [Point::RIGHT, Point::RIGHT, Point::UP, Point::LEFT] {
+//!     rope.insert(head, ());
+//!     head += step;
+//! }
+//! rope.insert(head, ());
+//! assert_eq!(rope.render('.', |_| '#'), ".##\n###\n");
+//! assert_eq!(rope.bounds().unwrap().min, Point::new(0, -1));
+//! ```
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::{Add, AddAssign, Index, IndexMut, Neg, Sub, SubAssign},
+};
+
+use crate::{err, Result};
+
+/// A position on a grid, or the offset between two.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+    pub const UP: Point = Point::new(0, -1);
+    pub const DOWN: Point = Point::new(0, 1);
+    pub const LEFT: Point = Point::new(-1, 0);
+    pub const RIGHT: Point = Point::new(1, 0);
+    /// Up, right, down and left.
+    pub const ORTHOGONAL: [Point; 4] = [Point::UP, Point::RIGHT, Point::DOWN, Point::LEFT];
+    /// The orthogonal and diagonal neighbours, clockwise from up.
+    pub const ADJACENT: [Point; 8] = [
+        Point::UP,
+        Point::new(1, -1),
+        Point::RIGHT,
+        Point::new(1, 1),
+        Point::DOWN,
+        Point::new(-1, 1),
+        Point::LEFT,
+        Point::new(-1, -1),
+    ];
+
+    #[must_use]
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// The four points sharing an edge with this one.
+    pub fn orthogonal(self) -> impl Iterator<Item = Point> {
+        Self::ORTHOGONAL.into_iter().map(move |step| self + step)
+    }
+
+    /// The eight points sharing an edge or a corner with this one.
+    pub fn adjacent(self) -> impl Iterator<Item = Point> {
+        Self::ADJACENT.into_iter().map(move |step| self + step)
+    }
+
+    #[must_use]
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The offset with each coordinate clamped to `-1..=1`.
+    #[must_use]
+    pub fn signum(self) -> Point {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/// The smallest rectangle holding a set of points, corners included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    /// The bounds of a single point.
+    #[must_use]
+    pub fn at(point: Point) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// Grow to take in `point`.
+    pub fn extend(&mut self, point: Point) {
+        self.min = Point::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    #[must_use]
+    pub fn width(&self) -> u64 {
+        self.max.x.abs_diff(self.min.x) + 1
+    }
+
+    #[must_use]
+    pub fn height(&self) -> u64 {
+        self.max.y.abs_diff(self.min.y) + 1
+    }
+
+    /// Every point inside, row by row from the top left.
+    pub fn points(self) -> impl Iterator<Item = Point> {
+        (self.min.y..=self.max.y)
+            .flat_map(move |y| (self.min.x..=self.max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+/// A rectangle of cells with `(0, 0)` at the top left.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// A grid from its rows, which must all be the same length.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Result<Self> {
+        let mut cells = Vec::new();
+        let (mut width, mut height) = (None, 0);
+        for row in rows {
+            match width {
+                None => width = Some(row.len()),
+                Some(width) if width != row.len() => {
+                    return Err(err!(
+                        "row {} has {} cells, not {}",
+                        height + 1,
+                        row.len(),
+                        width
+                    ))
+                }
+                Some(_) => {}
+            }
+            cells.extend(row);
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or_default(),
+            height,
+            cells,
+        })
+    }
+
+    /// A grid with a cell for each character of each line of `text`.
+    pub fn parse(text: &str, mut cell: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        Self::from_rows(
+            text.lines()
+                .map(|line| line.chars().map(&mut cell).collect::<Result<Vec<_>>>())
+                .collect::<Result<Vec<_>>>()?,
+        )
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        let x = usize::try_from(point.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(point.y).ok().filter(|&y| y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    fn point_of(&self, index: usize) -> Point {
+        // Grids that fit in memory have coordinates that fit in an `i64`.
+        #[allow(clippy::cast_possible_wrap)]
+        Point::new((index % self.width) as i64, (index / self.width) as i64)
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|index| &mut self.cells[index])
+    }
+
+    /// The bounds of the grid, or `None` if it's empty.
+    #[must_use]
+    pub fn bounds(&self) -> Option<Bounds> {
+        let last = self.cells.len().checked_sub(1)?;
+        Some(Bounds {
+            min: Point::ORIGIN,
+            max: self.point_of(last),
+        })
+    }
+
+    /// Every cell with its position, row by row from the top left.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| (self.point_of(index), cell))
+    }
+
+    /// The orthogonal neighbours of `point` that are on the grid.
+    pub fn orthogonal(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        point
+            .orthogonal()
+            .filter_map(|point| Some((point, self.get(point)?)))
+    }
+
+    /// The orthogonal and diagonal neighbours of `point` that are on the grid.
+    pub fn adjacent(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        point
+            .adjacent()
+            .filter_map(|point| Some((point, self.get(point)?)))
+    }
+
+    /// The grid drawn with one character per cell.
+    pub fn render(&self, glyph: impl Fn(&T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width.max(1)) {
+            out.extend(row.iter().map(&glyph));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point} is outside the grid"))
+    }
+}
+
+/// Cells at any points of an unbounded plane, negative coordinates included.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    bounds: Option<Bounds>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The box around every occupied point, or `None` if there are none.
+    #[must_use]
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    /// Set the cell at `point`, returning what was there.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        match &mut self.bounds {
+            Some(bounds) => bounds.extend(point),
+            None => self.bounds = Some(Bounds::at(point)),
+        }
+        self.cells.insert(point, value)
+    }
+
+    /// Clear the cell at `point`, shrinking the bounds if it was on their
+    /// edge.
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        let value = self.cells.remove(&point)?;
+        if self.bounds.is_some_and(|bounds| {
+            point.x == bounds.min.x
+                || point.x == bounds.max.x
+                || point.y == bounds.min.y
+                || point.y == bounds.max.y
+        }) {
+            self.bounds = bounds_of(self.cells.keys().copied());
+        }
+        Some(value)
+    }
+
+    /// Every occupied point and its cell, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&point, cell)| (point, cell))
+    }
+
+    /// Every point within the bounds, row by row from the top left, with its
+    /// cell if it's occupied.
+    pub fn rows(&self) -> impl Iterator<Item = (Point, Option<&T>)> {
+        self.bounds
+            .into_iter()
+            .flat_map(Bounds::points)
+            .map(|point| (point, self.get(point)))
+    }
+
+    /// The occupied orthogonal neighbours of `point`.
+    pub fn orthogonal(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        point
+            .orthogonal()
+            .filter_map(|point| Some((point, self.get(point)?)))
+    }
+
+    /// The occupied orthogonal and diagonal neighbours of `point`.
+    pub fn adjacent(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        point
+            .adjacent()
+            .filter_map(|point| Some((point, self.get(point)?)))
+    }
+
+    /// The bounds drawn with `glyph` for each occupied cell and `empty`
+    /// everywhere else; an empty grid draws as nothing.
+    pub fn render(&self, empty: char, glyph: impl Fn(&T) -> char) -> String {
+        let mut out = String::new();
+        let Some(bounds) = self.bounds else {
+            return out;
+        };
+        for (point, cell) in self.rows() {
+            out.push(cell.map_or(empty, &glyph));
+            if point.x == bounds.max.x {
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// A dense grid covering the bounds, with `fill` in the empty cells,
+    /// and the point that its `(0, 0)` stands for.
+    pub fn to_grid(&self, fill: T) -> Result<(Grid<T>, Point)>
+    where
+        T: Clone,
+    {
+        let Some(bounds) = self.bounds else {
+            return Ok((Grid::new(0, 0, fill), Point::ORIGIN));
+        };
+        let width = usize::try_from(bounds.width())?;
+        let height = usize::try_from(bounds.height())?;
+        let mut grid = Grid::new(width, height, fill);
+        for (&point, cell) in &self.cells {
+            grid[point - bounds.min] = cell.clone();
+        }
+        Ok((grid, bounds.min))
+    }
+}
+
+fn bounds_of(points: impl IntoIterator<Item = Point>) -> Option<Bounds> {
+    let mut points = points.into_iter();
+    let mut bounds = Bounds::at(points.next()?);
+    for point in points {
+        bounds.extend(point);
+    }
+    Some(bounds)
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        for (point, value) in iter {
+            self.insert(point, value);
+        }
+    }
+}
+
+/// Every cell of the dense grid, at the same points.
+impl<T> From<Grid<T>> for SparseGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        let points: Vec<_> = (0..grid.cells.len())
+            .map(|index| grid.point_of(index))
+            .collect();
+        points.into_iter().zip(grid.cells).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#.#\n.##\n";
+
+    fn map() -> Grid<bool> {
+        Grid::parse(MAP, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(err!("bad cell {:?}", c)),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_point() {
+        let mut point = Point::new(2, -3);
+        point += Point::DOWN;
+        assert_eq!(point, Point::new(2, -2));
+        assert_eq!(point - Point::new(5, 5), Point::new(-3, -7));
+        assert_eq!(-point, Point::new(-2, 2));
+        assert_eq!(Point::new(-3, 7).signum(), Point::new(-1, 1));
+        assert_eq!(point.manhattan(Point::ORIGIN), 4);
+        assert_eq!(point.orthogonal().count(), 4);
+        assert!(point
+            .adjacent()
+            .all(|p| p != point && (p - point).signum() == p - point));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let bounds = bounds_of([Point::new(-1, 2), Point::new(3, -2), Point::new(0, 0)]).unwrap();
+        assert_eq!(
+            (bounds.min, bounds.max),
+            (Point::new(-1, -2), Point::new(3, 2))
+        );
+        assert_eq!((bounds.width(), bounds.height()), (5, 5));
+        assert!(bounds.contains(Point::new(3, 2)));
+        assert!(!bounds.contains(Point::new(4, 2)));
+        let points: Vec<_> = Bounds {
+            min: Point::new(0, 0),
+            max: Point::new(1, 1),
+        }
+        .points()
+        .collect();
+        assert_eq!(points, [(0, 0), (1, 0), (0, 1), (1, 1)].map(Point::from));
+        assert_eq!(bounds_of([]), None);
+    }
+
+    #[test]
+    fn test_grid() {
+        let mut grid = map();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert!(grid[Point::new(2, 1)]);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.adjacent(Point::ORIGIN).filter(|(_, &c)| c).count(), 1);
+        assert_eq!(grid.orthogonal(Point::new(1, 1)).count(), 3);
+        grid[Point::new(1, 0)] = true;
+        assert_eq!(grid.render(|&c| if c { '#' } else { '.' }), "###\n.##\n");
+        assert_eq!(grid.bounds().map(|b| b.max), Some(Point::new(2, 1)));
+        assert!(Grid::parse("ab\nc", Ok).is_err());
+        assert_eq!(Grid::<char>::parse("", Ok).unwrap().bounds(), None);
+    }
+
+    #[test]
+    fn test_sparse_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.insert(Point::new(0, 0), 'a');
+        grid.insert(Point::new(-4, 2), 'b');
+        grid.insert(Point::new(1, -1), 'c');
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(
+            (bounds.min, bounds.max),
+            (Point::new(-4, -1), Point::new(1, 2))
+        );
+
+        assert_eq!(grid.remove(Point::new(-4, 2)), Some('b'));
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(
+            (bounds.min, bounds.max),
+            (Point::new(0, -1), Point::new(1, 0))
+        );
+        assert_eq!(grid.remove(Point::new(-4, 2)), None);
+        grid.remove(Point::new(0, 0));
+        grid.remove(Point::new(1, -1));
+        assert_eq!(grid.bounds(), None);
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn test_sparse_rows_and_render() {
+        let grid: SparseGrid<char> = [((-1, -1), 'a'), ((1, 0), 'b')]
+            .into_iter()
+            .map(|(point, c)| (Point::from(point), c))
+            .collect();
+        let rows: Vec<_> = grid.rows().map(|(_, cell)| cell.copied()).collect();
+        assert_eq!(rows, [Some('a'), None, None, None, None, Some('b')]);
+        assert_eq!(grid.render(' ', |&c| c), "a  \n  b\n");
+        assert_eq!(SparseGrid::<char>::new().render('.', |&c| c), "");
+        assert_eq!(grid.adjacent(Point::ORIGIN).count(), 2);
+        assert_eq!(grid.orthogonal(Point::ORIGIN).count(), 1);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let sparse = SparseGrid::from(map());
+        assert_eq!(sparse.len(), 6);
+        assert_eq!(sparse.render('?', |&c| if c { '#' } else { '.' }), MAP);
+
+        let elves: SparseGrid<bool> = sparse
+            .iter()
+            .filter(|(_, &c)| c)
+            .map(|(point, _)| (point - Point::new(5, 5), true))
+            .collect();
+        let (dense, origin) = elves.to_grid(false).unwrap();
+        assert_eq!(origin, Point::new(-5, -5));
+        assert_eq!(dense, map());
+        assert_eq!(SparseGrid::<u8>::new().to_grid(0).unwrap().0.width(), 0);
+    }
+}
@@ -0,0 +1,441 @@
+//! Iterator adaptors for idioms that keep coming up in puzzles.
+//!
+//! ```rust
+//! use aoc::iter::AocIterExt;
+//!
+//! let calories = [6000, 4000, 11000, 24000, 10000];
+//! assert_eq!(calories.iter().copied().top_k(3), [24000, 11000, 10000]);
+//! assert_eq!(calories.iter().copied().minmax(), Some((4000, 24000)));
+//! assert_eq!("mjqjpqmgbljsphdz".chars().first_distinct_window(4), Some(3));
+//!
+//! let total: Result<u32, _> = ["1", "2", "3"].iter().map(|s| s.parse::<u32>()).try_sum();
+//! assert_eq!(total.unwrap(), 6);
+//! ```
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap, HashMap, HashSet},
+    hash::{BuildHasher, Hash},
+    iter::{Product, Sum},
+};
+
+use crate::bitset::{Alphabet, BitSet, Word};
+
+/// Sets that can be narrowed to their intersection with another set.
+pub trait Intersect {
+    #[must_use]
+    fn intersect(self, other: &Self) -> Self;
+}
+
+impl<T: Eq + Hash, S: BuildHasher> Intersect for HashSet<T, S> {
+    fn intersect(mut self, other: &Self) -> Self {
+        self.retain(|item| other.contains(item));
+        self
+    }
+}
+
+impl<T: Ord> Intersect for BTreeSet<T> {
+    fn intersect(mut self, other: &Self) -> Self {
+        self.retain(|item| other.contains(item));
+        self
+    }
+}
+
+impl<A: Alphabet, W: Word> Intersect for BitSet<A, W> {
+    fn intersect(self, other: &Self) -> Self {
+        self & *other
+    }
+}
+
+/// Room for a heap that keeps the `k` best items of `iter`, plus the one
+/// pushed before the worst is popped; no more than `iter` can fill, so a huge
+/// `k` doesn't overflow.
+fn heap_capacity<I: Iterator>(iter: &I, k: usize) -> usize {
+    k.min(iter.size_hint().0).saturating_add(1)
+}
+
+/// Extension methods available on every [`Iterator`].
+pub trait AocIterExt: Iterator {
+    /// The `k` largest items in descending order, using a heap of size `k`
+    /// rather than sorting everything.
+    fn top_k(self, k: usize) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        let mut heap = BinaryHeap::with_capacity(heap_capacity(&self, k));
+        for item in self {
+            heap.push(Reverse(item));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+        // Ascending order of `Reverse` is descending order of the items
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(item)| item)
+            .collect()
+    }
+
+    /// The `k` smallest items in ascending order.
+    fn k_smallest(self, k: usize) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        let mut heap = BinaryHeap::with_capacity(heap_capacity(&self, k));
+        for item in self {
+            heap.push(item);
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+        heap.into_sorted_vec()
+    }
+
+    /// Intersect each consecutive group of `n` sets, dropping any incomplete
+    /// group at the end as [`slice::chunks_exact`] does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    fn chunked_intersection(self, n: usize) -> ChunkedIntersection<Self>
+    where
+        Self: Sized,
+        Self::Item: Intersect,
+    {
+        assert!(n > 0, "chunk size must be non-zero");
+        ChunkedIntersection { iter: self, n }
+    }
+
+    /// The index of the first item of the first run of `n` consecutive,
+    /// pairwise distinct items, in a single pass.
+    fn first_distinct_window(self, n: usize) -> Option<usize>
+    where
+        Self: Sized,
+        Self::Item: Eq + Hash,
+    {
+        if n == 0 {
+            return Some(0);
+        }
+        let mut last_seen = HashMap::new();
+        let mut run_start = 0;
+        for (idx, item) in self.enumerate() {
+            if let Some(prev) = last_seen.insert(item, idx) {
+                run_start = run_start.max(prev + 1);
+            }
+            if idx + 1 - run_start >= n {
+                return Some(idx + 1 - n);
+            }
+        }
+        None
+    }
+
+    /// Sum fallible items, stopping at the first error.
+    fn try_sum<T, E>(self) -> Result<T, E>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+        T: Sum<T>,
+    {
+        self.sum()
+    }
+
+    /// Multiply fallible items, stopping at the first error.
+    fn try_product<T, E>(self) -> Result<T, E>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+        T: Product<T>,
+    {
+        self.product()
+    }
+
+    /// The smallest and largest items in one pass, or `None` if empty.
+    fn minmax(mut self) -> Option<(Self::Item, Self::Item)>
+    where
+        Self: Sized,
+        Self::Item: Ord + Clone,
+    {
+        let first = self.next()?;
+        Some(self.fold((first.clone(), first), |(min, max), item| {
+            if item < min {
+                (item, max)
+            } else if item > max {
+                (min, item)
+            } else {
+                (min, max)
+            }
+        }))
+    }
+
+    /// How many times each item occurs.
+    fn counts(self) -> HashMap<Self::Item, usize>
+    where
+        Self: Sized,
+        Self::Item: Eq + Hash,
+    {
+        let mut counts = HashMap::new();
+        for item in self {
+            *counts.entry(item).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+impl<I: Iterator> AocIterExt for I {}
+
+/// Iterator returned by [`AocIterExt::chunked_intersection`].
+#[derive(Clone, Debug)]
+pub struct ChunkedIntersection<I> {
+    iter: I,
+    n: usize,
+}
+
+impl<I> Iterator for ChunkedIntersection<I>
+where
+    I: Iterator,
+    I::Item: Intersect,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.iter.next()?;
+        (1..self.n).try_fold(first, |acc, _| Some(acc.intersect(&self.iter.next()?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitset::Letters;
+
+    #[test]
+    fn test_top_k() {
+        let values = [5, 1, 9, 3, 7, 9];
+        assert_eq!(values.iter().copied().top_k(3), [9, 9, 7]);
+        assert_eq!(values.iter().copied().top_k(0), []);
+        assert_eq!(values.iter().copied().top_k(10).len(), 6);
+        assert_eq!(values.iter().copied().k_smallest(2), [1, 3]);
+        assert_eq!(values.iter().copied().top_k(usize::MAX).len(), 6);
+        assert_eq!(values.iter().copied().k_smallest(usize::MAX).len(), 6);
+    }
+
+    #[test]
+    fn test_chunked_intersection() {
+        let rucksacks = [
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "ttgJtRGJQctTZtZT",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+            "leftover",
+        ];
+
+        let badges: Vec<BitSet<Letters>> = rucksacks
+            .iter()
+            .map(|line| line.chars().collect())
+            .chunked_intersection(3)
+            .collect();
+        assert_eq!(badges.len(), 2);
+        assert_eq!(badges[0].iter().collect::<String>(), "r");
+        assert_eq!(badges[1].iter().collect::<String>(), "Z");
+
+        let badges: Vec<HashSet<char>> = rucksacks
+            .iter()
+            .map(|line| line.chars().collect())
+            .chunked_intersection(3)
+            .collect();
+        assert_eq!(badges, [HashSet::from(['r']), HashSet::from(['Z'])]);
+    }
+
+    #[test]
+    fn test_first_distinct_window() {
+        let cases = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+        for (input, four, fourteen) in cases {
+            assert_eq!(input.chars().first_distinct_window(4), Some(four - 4));
+            assert_eq!(input.chars().first_distinct_window(14), Some(fourteen - 14));
+        }
+        assert_eq!("aab".chars().first_distinct_window(3), None);
+        assert_eq!("abc".chars().first_distinct_window(0), Some(0));
+    }
+
+    #[test]
+    fn test_try_sum_product() {
+        let ok: Result<u32, String> = [Ok(2), Ok(3), Ok(4)].into_iter().try_sum();
+        assert_eq!(ok, Ok(9));
+        let product: Result<u32, String> = [Ok(2), Ok(3), Ok(4)].into_iter().try_product();
+        assert_eq!(product, Ok(24));
+        let err: Result<u32, &str> = [Ok(2), Err("bad"), Ok(4)].into_iter().try_sum();
+        assert_eq!(err, Err("bad"));
+    }
+
+    #[test]
+    fn test_minmax_counts() {
+        assert_eq!([3, 1, 4, 1, 5].into_iter().minmax(), Some((1, 5)));
+        assert_eq!([7].into_iter().minmax(), Some((7, 7)));
+        assert_eq!(std::iter::empty::<u8>().minmax(), None);
+
+        let counts = "hello".chars().counts();
+        assert_eq!(counts[&'l'], 2);
+        assert_eq!(counts[&'h'], 1);
+        assert_eq!(counts.len(), 4);
+    }
+}
+
+/// Each adaptor against the hand-rolled version it replaces in the days.
+#[cfg(all(test, feature = "bench"))]
+mod benches {
+    extern crate test;
+    use super::*;
+    use crate::bitset::{Letters, Lower};
+    use test::{black_box, Bencher};
+
+    /// Deterministic pseudo-random numbers, so runs are comparable.
+    fn numbers(count: usize) -> Vec<u32> {
+        let mut state: u32 = 12_345;
+        (0..count)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                state >> 8
+            })
+            .collect()
+    }
+
+    fn letters(count: usize, alphabet: &[u8]) -> String {
+        numbers(count)
+            .into_iter()
+            .map(|n| alphabet[n as usize % alphabet.len()] as char)
+            .collect()
+    }
+
+    #[bench]
+    fn bench_top3_sort(b: &mut Bencher) {
+        let values = numbers(2_000);
+        b.iter(|| {
+            let mut vals = black_box(&values).clone();
+            vals.sort_unstable();
+            vals.iter().rev().take(3).sum::<u32>()
+        });
+    }
+
+    #[bench]
+    fn bench_top3_top_k(b: &mut Bencher) {
+        let values = numbers(2_000);
+        b.iter(|| {
+            black_box(&values)
+                .iter()
+                .copied()
+                .top_k(3)
+                .into_iter()
+                .sum::<u32>()
+        });
+    }
+
+    fn rucksacks() -> Vec<String> {
+        let alphabet = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        let text = letters(300 * 24, alphabet);
+        text.as_bytes()
+            .chunks(24)
+            .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+            .collect()
+    }
+
+    #[bench]
+    fn bench_badges_hashset_reduce(b: &mut Bencher) {
+        let lines = rucksacks();
+        b.iter(|| {
+            let sets: Vec<HashSet<char>> = black_box(&lines)
+                .iter()
+                .map(|line| line.chars().collect())
+                .collect();
+            sets.chunks_exact(3)
+                .map(|chunk| {
+                    chunk
+                        .iter()
+                        .cloned()
+                        .reduce(|acc, set| acc.intersection(&set).copied().collect())
+                        .map_or(0, |set| set.len())
+                })
+                .sum::<usize>()
+        });
+    }
+
+    #[bench]
+    fn bench_badges_chunked_intersection(b: &mut Bencher) {
+        let lines = rucksacks();
+        b.iter(|| {
+            black_box(&lines)
+                .iter()
+                .map(|line| line.chars().collect::<BitSet<Letters>>())
+                .chunked_intersection(3)
+                .map(|set| set.len())
+                .sum::<usize>()
+        });
+    }
+
+    fn signal() -> String {
+        // Small alphabet so the marker comes late
+        let mut signal = letters(4_000, b"abcdefghijklm");
+        signal.push_str("nopqrstuvwxyz");
+        signal
+    }
+
+    #[bench]
+    fn bench_marker_hashset_windows(b: &mut Bencher) {
+        let signal = signal();
+        b.iter(|| {
+            let chars: Vec<_> = black_box(&signal).chars().collect();
+            chars.windows(14).position(|window| {
+                let set: HashSet<&char> = window.iter().collect();
+                set.len() == 14
+            })
+        });
+    }
+
+    #[bench]
+    fn bench_marker_bitset_windows(b: &mut Bencher) {
+        let signal = signal();
+        b.iter(|| {
+            black_box(&signal)
+                .as_bytes()
+                .windows(14)
+                .position(|window| window.iter().copied().collect::<BitSet<Lower>>().len() == 14)
+        });
+    }
+
+    #[bench]
+    fn bench_marker_first_distinct_window(b: &mut Bencher) {
+        let signal = signal();
+        b.iter(|| black_box(&signal).bytes().first_distinct_window(14));
+    }
+
+    // The hand-rolled version from d01, kept as is for comparison
+    #[allow(clippy::manual_try_fold)]
+    #[bench]
+    fn bench_sum_fold(b: &mut Bencher) {
+        let lines: Vec<String> = numbers(2_000).iter().map(ToString::to_string).collect();
+        b.iter(|| {
+            black_box(&lines)
+                .iter()
+                .fold(Ok::<_, std::num::ParseIntError>(0_u64), |acc, line| {
+                    Ok(acc? + line.parse::<u64>()?)
+                })
+        });
+    }
+
+    #[bench]
+    fn bench_sum_try_sum(b: &mut Bencher) {
+        let lines: Vec<String> = numbers(2_000).iter().map(ToString::to_string).collect();
+        b.iter(|| {
+            black_box(&lines)
+                .iter()
+                .map(|line| line.parse::<u64>())
+                .try_sum::<u64, _>()
+        });
+    }
+}
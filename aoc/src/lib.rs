@@ -1,3 +1,22 @@
+#![cfg_attr(all(test, feature = "bench"), feature(test))]
+// Lets code generated by `aoc-derive` refer to `::aoc` from within this crate.
+extern crate self as aoc;
+
+pub mod bitset;
+pub mod cycle;
+pub mod diagnostic;
 pub mod errors;
 pub use errors::{Error, Result};
 pub mod files;
+pub mod grid;
+pub mod iter;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub use parse::AocParse;
+pub mod ranges;
+pub mod solution;
+pub mod store;
+pub mod text;
+pub mod trace;
+pub mod union_find;
@@ -0,0 +1,338 @@
+//! Number theory for puzzles that cycle or wrap around, and arithmetic that
+//! fails with an [`Error`](crate::Error) instead of wrapping or panicking when
+//! an answer outgrows its type.
+//!
+//! ```rust
+//! use aoc::math::{checked_sum, crt, lcm_all, wrap_index};
+//!
+//! // Day 11: worry levels only matter modulo every monkey's divisor.
+//! assert_eq!(lcm_all(&[23u64, 19, 13, 17]).unwrap(), 96_577);
+//!
+//! // Day 20: moving an item -3 places round a list of 7.
+//! assert_eq!(wrap_index(1 - 3, 7), 5);
+//!
+//! // x = 2 (mod 3), x = 3 (mod 5), x = 2 (mod 7)
+//! assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]).unwrap(), (23, 105));
+//!
+//! assert!(checked_sum([u32::MAX, 1]).is_err());
+//! ```
+use std::{
+    any,
+    fmt::{Debug, Display},
+    ops::{Div, Mul, Neg, Rem, Sub},
+};
+
+use crate::{err, Result};
+
+/// The primitive integer types.
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Display
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+/// The signed primitive integer types.
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+macro_rules! impl_integer {
+    ($($ty:ty),*) => {
+        $(impl Integer for $ty {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$ty>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$ty>::checked_sub(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$ty>::checked_mul(self, rhs)
+            }
+
+            fn rem_euclid(self, rhs: Self) -> Self {
+                <$ty>::rem_euclid(self, rhs)
+            }
+        })*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+/// `a + b`, or an error if it overflows.
+pub fn checked_add<T: Integer>(a: T, b: T) -> Result<T> {
+    a.checked_add(b)
+        .ok_or_else(|| err!("{} + {} overflows {}", a, b, any::type_name::<T>()))
+}
+
+/// `a - b`, or an error if it overflows.
+pub fn checked_sub<T: Integer>(a: T, b: T) -> Result<T> {
+    a.checked_sub(b)
+        .ok_or_else(|| err!("{} - {} overflows {}", a, b, any::type_name::<T>()))
+}
+
+/// `a * b`, or an error if it overflows.
+pub fn checked_mul<T: Integer>(a: T, b: T) -> Result<T> {
+    a.checked_mul(b)
+        .ok_or_else(|| err!("{} * {} overflows {}", a, b, any::type_name::<T>()))
+}
+
+/// The sum of `items`, or an error if it overflows.
+pub fn checked_sum<T: Integer>(items: impl IntoIterator<Item = T>) -> Result<T> {
+    items.into_iter().try_fold(T::ZERO, checked_add)
+}
+
+/// The product of `items`, or an error if it overflows.
+pub fn checked_product<T: Integer>(items: impl IntoIterator<Item = T>) -> Result<T> {
+    items.into_iter().try_fold(T::ONE, checked_mul)
+}
+
+/// `a` modulo `m`, in `0..m` even when `a` is negative.
+///
+/// # Panics
+///
+/// Panics if `m` is zero.
+#[must_use]
+pub fn modulo<T: Integer>(a: T, m: T) -> T {
+    a.rem_euclid(m)
+}
+
+/// Where `offset` lands in a cyclic list of `len` items, for any offset.
+///
+/// # Panics
+///
+/// Panics if `len` is zero.
+#[must_use]
+pub fn wrap_index(offset: i64, len: usize) -> usize {
+    // Lengths of lists that fit in memory fit in an `i128`, and the result
+    // is less than `len`.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    {
+        i128::from(offset).rem_euclid(len as i128) as usize
+    }
+}
+
+/// The greatest common divisor of `a` and `b`, which is never negative.
+#[must_use]
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    if a < T::ZERO {
+        T::ZERO - a
+    } else {
+        a
+    }
+}
+
+/// The least common multiple of `a` and `b`, or an error if it overflows.
+pub fn lcm<T: Integer>(a: T, b: T) -> Result<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Ok(T::ZERO);
+    }
+    let lcm = checked_mul(a / gcd(a, b), b)?;
+    Ok(if lcm < T::ZERO { T::ZERO - lcm } else { lcm })
+}
+
+/// The greatest common divisor of all of `values`, or zero if there are
+/// none.
+#[must_use]
+pub fn gcd_all<T: Integer>(values: &[T]) -> T {
+    values.iter().fold(T::ZERO, |acc, &value| gcd(acc, value))
+}
+
+/// The least common multiple of all of `values`, or one if there are none.
+pub fn lcm_all<T: Integer>(values: &[T]) -> Result<T> {
+    values
+        .iter()
+        .try_fold(T::ONE, |acc, &value| lcm(acc, value))
+}
+
+/// `(g, x, y)` such that `a * x + b * y = g`, where `g` is the greatest
+/// common divisor of `a` and `b`.
+#[must_use]
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::ONE, T::ZERO);
+    let (mut y0, mut y1) = (T::ZERO, T::ONE);
+    while r1 != T::ZERO {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < T::ZERO {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// The inverse of `a` modulo `m`, in `0..m`, or an error if they share a
+/// factor.
+pub fn mod_inverse(a: i64, m: i64) -> Result<i64> {
+    if m <= 0 {
+        return Err(err!("modulus {} isn't positive", m));
+    }
+    let (g, x, _) = extended_gcd(i128::from(a), i128::from(m));
+    if g != 1 {
+        return Err(err!("{} has no inverse modulo {}", a, m));
+    }
+    Ok(i64::try_from(x.rem_euclid(i128::from(m)))?)
+}
+
+/// `base` to the power `exp`, modulo `m`, without overflowing.
+///
+/// # Panics
+///
+/// Panics if `m` is zero.
+#[must_use]
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    let m = u128::from(m);
+    let mut base = u128::from(base) % m;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    u64::try_from(result).expect("less than a u64 modulus")
+}
+
+/// The smallest `x >= 0` with `x = r (mod m)` for every `(r, m)` in
+/// `congruences`, and the modulus it repeats with. The moduli needn't be
+/// coprime, but an error is returned if the congruences contradict each
+/// other or the combined modulus overflows.
+pub fn crt(congruences: &[(i64, i64)]) -> Result<(i64, i64)> {
+    let (mut x, mut modulus) = (0_i128, 1_i128);
+    for &(r, m) in congruences {
+        if m <= 0 {
+            return Err(err!("modulus {} isn't positive", m));
+        }
+        let (r, m) = (i128::from(r).rem_euclid(i128::from(m)), i128::from(m));
+        let (g, p, _) = extended_gcd(modulus, m);
+        if (r - x) % g != 0 {
+            return Err(err!(
+                "x = {} (mod {}) contradicts x = {} (mod {})",
+                r,
+                m,
+                x,
+                modulus
+            ));
+        }
+        let step = m / g;
+        // Solve x + modulus * k = r (mod m) for k, reducing as we go so
+        // nothing here outgrows an i128.
+        let k = ((r - x) / g % step * (p % step)).rem_euclid(step);
+        x += modulus * k;
+        modulus *= step;
+        if i64::try_from(modulus).is_err() {
+            return Err(err!("combined modulus {} overflows i64", modulus));
+        }
+        x = x.rem_euclid(modulus);
+    }
+    Ok((i64::try_from(x)?, i64::try_from(modulus)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked() {
+        assert_eq!(checked_add(2u8, 3).unwrap(), 5);
+        assert_eq!(
+            checked_add(200u8, 100).unwrap_err().to_string(),
+            "200 + 100 overflows u8"
+        );
+        assert!(checked_sub(2u32, 3).is_err());
+        assert_eq!(checked_sub(2i32, 3).unwrap(), -1);
+        assert!(checked_mul(i64::MAX, 2).is_err());
+        assert_eq!(checked_sum([1u32, 2, 3]).unwrap(), 6);
+        assert_eq!(checked_sum(Vec::<u32>::new()).unwrap(), 0);
+        assert!(checked_sum([u32::MAX, 1]).is_err());
+        assert_eq!(checked_product([2u64, 3, 7]).unwrap(), 42);
+        assert!(checked_product([1u64 << 32, 1 << 32]).is_err());
+    }
+
+    #[test]
+    fn test_modulo() {
+        assert_eq!(modulo(-7i64, 3), 2);
+        assert_eq!(modulo(7u32, 3), 1);
+        assert_eq!(wrap_index(-1, 5), 4);
+        assert_eq!(wrap_index(12, 5), 2);
+        assert_eq!(wrap_index(i64::MIN, 3), 1);
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12i32, 18), 6);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(lcm(4i64, -6).unwrap(), 12);
+        assert_eq!(lcm(0u32, 5).unwrap(), 0);
+        assert!(lcm(u64::MAX, u64::MAX - 1).is_err());
+        assert_eq!(gcd_all(&[12u32, 18, 27]), 3);
+        assert_eq!(gcd_all::<u32>(&[]), 0);
+        assert_eq!(lcm_all(&[2u32, 3, 4, 5]).unwrap(), 60);
+        assert_eq!(lcm_all::<u32>(&[]).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240i64, 46), (-240, 46), (7, 0), (0, -7), (17, 5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b), "{a} {b}");
+            assert_eq!(a * x + b * y, g, "{a} {b}");
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse_and_pow() {
+        assert_eq!(mod_inverse(3, 11).unwrap(), 4);
+        assert_eq!(mod_inverse(-3, 11).unwrap(), 7);
+        assert!(mod_inverse(6, 9).is_err());
+        assert!(mod_inverse(3, 0).is_err());
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]).unwrap(), (23, 105));
+        assert_eq!(crt(&[(-1, 4), (3, 6)]).unwrap(), (3, 12));
+        assert_eq!(crt(&[]).unwrap(), (0, 1));
+        assert!(crt(&[(1, 4), (2, 6)]).is_err());
+        assert!(crt(&[(1, 0)]).is_err());
+        let primes = [1_000_000_007, 1_000_000_009, 998_244_353];
+        assert!(crt(&primes.map(|p| (1, p))).is_err());
+        let (x, m) = crt(&[(5, 1_000_000_007), (7, 1_000_000_009)]).unwrap();
+        assert_eq!((x % 1_000_000_007, x % 1_000_000_009), (5, 7));
+        assert_eq!(m, 1_000_000_016_000_000_063);
+    }
+}
@@ -0,0 +1,233 @@
+//! A cache for recursive functions, such as a search over game states or
+//! the size of each directory in a tree.
+//!
+//! The function is a closure, so it can borrow whatever it needs. It gets a
+//! [`Recurse`] handle to call itself through, which looks the answer up in
+//! the cache first:
+//!
+//! ```rust
+//! use aoc::memo::Memo;
+//!
+//! let coins = [1, 5, 10, 25];
+//! let mut ways = Memo::new();
+//! // Ways to make `amount` from the coins at `first` onwards.
+//! let count = ways.get((100, 0), &|ways, (amount, first)| -> u64 {
+//!     match (amount, coins.get(first)) {
+//!         (0, _) => 1,
+//!         (_, None) => 0,
+//!         (_, Some(&coin)) if coin > amount => ways.call((amount, first + 1)),
+//!         (_, Some(&coin)) => ways.call((amount - coin, first)) + ways.call((amount, first + 1)),
+//!     }
+//! });
+//! assert_eq!(count, 242);
+//! assert!(ways.stats().hits > 0);
+//! ```
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+/// How often a [`Memo`] found an answer in its cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl Stats {
+    /// The fraction of lookups that were hits, or 0 before any.
+    #[must_use]
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            // Precise enough for any count of lookups worth reporting.
+            #[allow(clippy::cast_precision_loss)]
+            {
+                self.hits as f64 / lookups as f64
+            }
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+/// The answers of a function of `K` computed so far, keyed with the hasher
+/// `S`.
+#[derive(Clone, Debug)]
+pub struct Memo<K, V, S = RandomState> {
+    cache: HashMap<K, V, S>,
+    stats: Stats,
+}
+
+impl<K, V> Memo<K, V> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, S> Memo<K, V, S> {
+    /// An empty cache hashing its keys with `hasher`, which can be much
+    /// faster than the default for small keys.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            cache: HashMap::with_hasher(hasher),
+            stats: Stats::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// How many answers are cached.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Forget every answer, keeping the statistics.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl<K, V, S> Memo<K, V, S>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    S: BuildHasher,
+{
+    /// The cached answer for `key`, if there is one, without counting a
+    /// lookup.
+    pub fn cached(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// The answer of `f` for `key`, from the cache if it's there. `f` calls
+    /// itself for other keys with [`Recurse::call`]; every call to `get` on
+    /// the same cache should pass the same function.
+    pub fn get(&mut self, key: K, f: &Function<'_, K, V, S>) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+        self.stats.misses += 1;
+        let value = f(&mut Recurse { memo: self, f }, key.clone());
+        self.cache.insert(key, value.clone());
+        value
+    }
+}
+
+/// A function that [`Memo::get`] can cache.
+pub type Function<'f, K, V, S = RandomState> = dyn Fn(&mut Recurse<'_, K, V, S>, K) -> V + 'f;
+
+/// What a function cached by [`Memo::get`] calls itself through.
+pub struct Recurse<'a, K, V, S = RandomState> {
+    memo: &'a mut Memo<K, V, S>,
+    f: &'a Function<'a, K, V, S>,
+}
+
+impl<K, V, S> Recurse<'_, K, V, S>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    S: BuildHasher,
+{
+    /// The function's answer for `key`, from the cache if it's there.
+    pub fn call(&mut self, key: K) -> V {
+        self.memo.get(key, self.f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::Cell,
+        hash::{BuildHasherDefault, DefaultHasher},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_fibonacci() {
+        let mut memo = Memo::new();
+        let fib = |fib: &mut Recurse<u64, u128>, n| {
+            if n < 2 {
+                u128::from(n)
+            } else {
+                fib.call(n - 1) + fib.call(n - 2)
+            }
+        };
+        assert_eq!(
+            memo.get(150, &fib),
+            9_969_216_677_189_303_386_214_405_760_200
+        );
+        assert_eq!(memo.len(), 151);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 148,
+                misses: 151
+            }
+        );
+        assert_eq!(memo.cached(&10), Some(&55));
+
+        assert_eq!(memo.get(10, &fib), 55);
+        assert_eq!(memo.stats().hits, 149);
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.cached(&10), None);
+    }
+
+    #[test]
+    fn test_borrowed_context() {
+        // A tree as a list of children, borrowed by the closure.
+        let children: Vec<Vec<usize>> = vec![vec![1, 2], vec![3], vec![3], vec![]];
+        let calls = Cell::new(0);
+        let mut memo = Memo::with_hasher(BuildHasherDefault::<DefaultHasher>::default());
+        let paths = |paths: &mut Recurse<usize, u32, _>, node: usize| -> u32 {
+            calls.set(calls.get() + 1);
+            if children[node].is_empty() {
+                1
+            } else {
+                children[node].iter().map(|&child| paths.call(child)).sum()
+            }
+        };
+        assert_eq!(memo.get(0, &paths), 2);
+        assert_eq!(calls.get(), 4);
+        assert_eq!(memo.stats().hits, 1);
+    }
+
+    #[test]
+    fn test_stats_display() {
+        assert_eq!(Stats::default().hit_rate(), 0.0);
+        let stats = Stats { hits: 3, misses: 1 };
+        assert_eq!(stats.to_string(), "3 hits, 1 misses (75.0% hit rate)");
+    }
+}
@@ -0,0 +1,571 @@
+//! Parsing helpers for puzzle input.
+//!
+//! [`AocParse`] derives [`FromStr`] from a format pattern, where each
+//! `{field}` is parsed with that field's own `FromStr` implementation and
+//! everything else must match literally:
+//!
+//! ```rust
+//! use aoc::AocParse;
+//!
+//! #[derive(AocParse, Debug, PartialEq)]
+//! #[aoc(pattern = "move {mv} from {from} to {to}")]
+//! struct Instruction {
+//!     mv: usize,
+//!     from: usize,
+//!     to: usize,
+//! }
+//!
+//! let instruction: Instruction = "move 1 from 2 to 3".parse().unwrap();
+//! assert_eq!(instruction, Instruction { mv: 1, from: 2, to: 3 });
+//!
+//! let err = "move one from 2 to 3".parse::<Instruction>().unwrap_err();
+//! assert_eq!(
+//!     err.to_string(),
+//!     r#"Instruction: field `mv` could not parse "one" at column 6: invalid digit found in string"#
+//! );
+//! ```
+//!
+//! Tuple fields are referred to by index (`{0}`), and enums take one pattern
+//! per variant, tried in declaration order:
+//!
+//! ```rust
+//! use aoc::AocParse;
+//!
+//! #[derive(AocParse, Debug, PartialEq)]
+//! enum Line {
+//!     #[aoc(pattern = "$ cd {0}")]
+//!     Cd(String),
+//!     #[aoc(pattern = "$ ls")]
+//!     Ls,
+//!     #[aoc(pattern = "{size} {name}")]
+//!     File { size: u32, name: String },
+//! }
+//!
+//! assert_eq!("$ ls".parse::<Line>().unwrap(), Line::Ls);
+//! assert_eq!("$ cd a".parse::<Line>().unwrap(), Line::Cd("a".into()));
+//! ```
+//!
+//! A field runs up to the first occurrence of the literal that follows it, or
+//! to the end of the line if it comes last, so two fields must always be
+//! separated by a literal. `{{` and `}}` match literal braces. A pattern
+//! that can't be read, such as one with an unclosed `{`, fails to compile:
+//!
+//! ```compile_fail
+//! use aoc::AocParse;
+//!
+//! #[derive(AocParse)]
+//! #[aoc(pattern = "move {n")]
+//! struct Move {
+//!     n: u32,
+//! }
+//! ```
+//!
+//! Lines that are mostly prose around some numbers can skip the pattern and
+//! pull out every integer with [`ints`], or exactly `N` of them with
+//! [`ints_n`]. A `-` is a sign for signed types, unless it follows a letter
+//! or digit, so ranges like `2-8` stay positive:
+//!
+//! ```rust
+//! use aoc::parse::{ints, ints_n};
+//!
+//! let line = "Sensor at x=2, y=-18: closest beacon is at x=-2, y=15";
+//! assert_eq!(ints_n::<i64, 4>(line).unwrap(), [2, -18, -2, 15]);
+//! assert_eq!(ints::<u32>("2-8,3-7").unwrap(), [2, 8, 3, 7]);
+//!
+//! let err = ints_n::<u8, 3>("move 1 from 2").unwrap_err();
+//! assert_eq!(err.to_string(), r#"ints: expected 3 numbers, found 2 in "move 1 from 2""#);
+//! ```
+use std::{any, error, fmt, str::FromStr};
+
+use crate::Error;
+
+pub use aoc_derive::AocParse;
+
+/// Why a line failed to parse with an [`AocParse`] pattern.
+#[derive(Debug)]
+pub enum ParseErrorKind {
+    /// The literal part of the pattern did not match.
+    Literal { expected: &'static str },
+    /// The literal that ends a field never appears.
+    Missing {
+        expected: &'static str,
+        after: &'static str,
+    },
+    /// The text for a field was found but its `FromStr` failed.
+    Field { name: &'static str, source: Error },
+    /// The pattern matched but input was left over.
+    Trailing,
+    /// No enum variant came close to matching.
+    NoMatch { patterns: Vec<&'static str> },
+    /// [`ints_n`] found the wrong number of integers.
+    Count { expected: usize, found: usize },
+}
+
+/// An error from an [`AocParse`]-derived `FromStr`, recording the type (or
+/// enum variant) being parsed, the offending line, and the byte range within
+/// it that failed.
+#[derive(Debug)]
+pub struct ParseError {
+    pub ty: &'static str,
+    pub line: String,
+    pub offset: usize,
+    pub len: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// Choose the most useful error from a failed attempt at each enum
+    /// variant: a field error from the variant that matched the furthest, or
+    /// otherwise a summary of all the patterns.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn best_of(
+        ty: &'static str,
+        line: &str,
+        patterns: &[&'static str],
+        errors: Vec<ParseError>,
+    ) -> Self {
+        errors
+            .into_iter()
+            .filter(|e| matches!(e.kind, ParseErrorKind::Field { .. }))
+            .max_by_key(|e| e.offset)
+            .unwrap_or_else(|| ParseError {
+                ty,
+                line: line.to_string(),
+                offset: 0,
+                len: line.len(),
+                kind: ParseErrorKind::NoMatch {
+                    patterns: patterns.to_vec(),
+                },
+            })
+    }
+
+    /// The 1-based column (in characters) where the error starts.
+    #[must_use]
+    pub fn column(&self) -> usize {
+        self.line[..self.offset].chars().count() + 1
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { ty, line, .. } = self;
+        let column = self.column();
+        match &self.kind {
+            ParseErrorKind::Literal { expected } => {
+                let found = &line[self.offset..];
+                write!(
+                    f,
+                    "{ty}: expected {expected:?} at column {column}, found {found:?}"
+                )
+            }
+            ParseErrorKind::Missing { expected, after } => {
+                let found = &line[self.offset..];
+                write!(
+                    f,
+                    "{ty}: expected {expected:?} after field `{after}` at column {column}, found {found:?}"
+                )
+            }
+            ParseErrorKind::Field { name, source } => {
+                let value = &line[self.offset..self.offset + self.len];
+                write!(
+                    f,
+                    "{ty}: field `{name}` could not parse {value:?} at column {column}: {source}"
+                )
+            }
+            ParseErrorKind::Trailing => {
+                let rest = &line[self.offset..];
+                write!(
+                    f,
+                    "{ty}: unexpected trailing input {rest:?} at column {column}"
+                )
+            }
+            ParseErrorKind::NoMatch { patterns } => {
+                write!(f, "{ty}: {line:?} did not match any of {patterns:?}")
+            }
+            ParseErrorKind::Count { expected, found } => {
+                write!(
+                    f,
+                    "{ty}: expected {expected} numbers, found {found} in {line:?}"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.kind {
+            ParseErrorKind::Field { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Walks a line according to a pattern; used by the code that
+/// [`AocParse`] generates.
+#[doc(hidden)]
+pub struct Cursor<'a> {
+    ty: &'static str,
+    line: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    #[must_use]
+    pub fn new(ty: &'static str, line: &'a str) -> Self {
+        Self { ty, line, pos: 0 }
+    }
+
+    fn error(&self, offset: usize, len: usize, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            ty: self.ty,
+            line: self.line.to_string(),
+            offset,
+            len,
+            kind,
+        }
+    }
+
+    /// Consume `expected` or fail.
+    pub fn literal(&mut self, expected: &'static str) -> Result<(), ParseError> {
+        if self.line[self.pos..].starts_with(expected) {
+            self.pos += expected.len();
+            Ok(())
+        } else {
+            Err(self.error(
+                self.pos,
+                self.line.len() - self.pos,
+                ParseErrorKind::Literal { expected },
+            ))
+        }
+    }
+
+    /// Parse the text up to the next occurrence of `until` (or the end of the
+    /// line) as a `T`, leaving `until` itself unconsumed.
+    pub fn field<T>(
+        &mut self,
+        name: &'static str,
+        until: Option<&'static str>,
+    ) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Into<Error>,
+    {
+        let rest = &self.line[self.pos..];
+        let len = match until {
+            Some(expected) => rest.find(expected).ok_or_else(|| {
+                self.error(
+                    self.pos,
+                    rest.len(),
+                    ParseErrorKind::Missing {
+                        expected,
+                        after: name,
+                    },
+                )
+            })?,
+            None => rest.len(),
+        };
+        let value = rest[..len].parse().map_err(|e: T::Err| {
+            self.error(
+                self.pos,
+                len,
+                ParseErrorKind::Field {
+                    name,
+                    source: e.into(),
+                },
+            )
+        })?;
+        self.pos += len;
+        Ok(value)
+    }
+
+    /// Fail if any input remains.
+    pub fn finish(self) -> Result<(), ParseError> {
+        if self.pos == self.line.len() {
+            Ok(())
+        } else {
+            Err(self.error(
+                self.pos,
+                self.line.len() - self.pos,
+                ParseErrorKind::Trailing,
+            ))
+        }
+    }
+}
+
+/// An integer type that [`ints`] and [`ints_n`] can extract.
+pub trait Int: FromStr<Err = std::num::ParseIntError> + Copy + Default {
+    /// Whether a leading `-` belongs to the number.
+    const SIGNED: bool;
+}
+
+macro_rules! impl_int {
+    ($signed:literal: $($ty:ty),*) => {
+        $(impl Int for $ty {
+            const SIGNED: bool = $signed;
+        })*
+    };
+}
+
+impl_int!(true: i8, i16, i32, i64, i128, isize);
+impl_int!(false: u8, u16, u32, u64, u128, usize);
+
+/// The integers in a line, as byte offsets and the text of each.
+struct Numbers<'a> {
+    line: &'a str,
+    pos: usize,
+    signed: bool,
+}
+
+impl<'a> Iterator for Numbers<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.line.as_bytes();
+        let digit = self.pos + bytes[self.pos..].iter().position(u8::is_ascii_digit)?;
+        let end = bytes[digit..]
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .map_or(bytes.len(), |len| digit + len);
+        let start = match digit.checked_sub(1) {
+            Some(sign)
+                if self.signed
+                    && bytes[sign] == b'-'
+                    && !sign
+                        .checked_sub(1)
+                        .is_some_and(|before| bytes[before].is_ascii_alphanumeric()) =>
+            {
+                sign
+            }
+            _ => digit,
+        };
+        self.pos = end;
+        Some((start, &self.line[start..end]))
+    }
+}
+
+fn numbers<T: Int>(line: &str) -> Numbers<'_> {
+    Numbers {
+        line,
+        pos: 0,
+        signed: T::SIGNED,
+    }
+}
+
+fn parse_number<T: Int>(line: &str, offset: usize, text: &str) -> Result<T, ParseError> {
+    text.parse().map_err(|e| ParseError {
+        ty: "ints",
+        line: line.to_string(),
+        offset,
+        len: text.len(),
+        kind: ParseErrorKind::Field {
+            name: any::type_name::<T>(),
+            source: Error::from(e),
+        },
+    })
+}
+
+/// Every integer in `line`, ignoring whatever is around them.
+pub fn ints<T: Int>(line: &str) -> Result<Vec<T>, ParseError> {
+    numbers::<T>(line)
+        .map(|(offset, text)| parse_number(line, offset, text))
+        .collect()
+}
+
+/// The integers in `line`, which must have exactly `N` of them, without
+/// allocating unless it fails.
+pub fn ints_n<T: Int, const N: usize>(line: &str) -> Result<[T; N], ParseError> {
+    let mut out = [T::default(); N];
+    let mut numbers = numbers::<T>(line);
+    let mut found = 0;
+    for (slot, (offset, text)) in out.iter_mut().zip(numbers.by_ref()) {
+        *slot = parse_number(line, offset, text)?;
+        found += 1;
+    }
+    found += numbers.count();
+    if found == N {
+        Ok(out)
+    } else {
+        Err(ParseError {
+            ty: "ints",
+            line: line.to_string(),
+            offset: 0,
+            len: line.len(),
+            kind: ParseErrorKind::Count { expected: N, found },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(pattern = "move {mv} from {from} to {to}")]
+    struct Instruction {
+        mv: usize,
+        from: usize,
+        to: usize,
+    }
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(pattern = "{0}-{1}")]
+    struct Span(u32, u32);
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(pattern = "{{{0}}}")]
+    struct Braced(char);
+
+    #[derive(AocParse, Debug, PartialEq)]
+    enum Line {
+        #[aoc(pattern = "$ cd {0}")]
+        Cd(String),
+        #[aoc(pattern = "$ ls")]
+        Ls,
+        #[aoc(pattern = "dir {name}")]
+        Dir { name: String },
+        #[aoc(pattern = "{0} {1}")]
+        File(u32, String),
+    }
+
+    fn parse_err<T>(s: &str) -> ParseError
+    where
+        T: FromStr<Err = Error> + fmt::Debug,
+    {
+        *s.parse::<T>()
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_struct() {
+        assert_eq!(
+            "move 11 from 2 to 3".parse::<Instruction>().unwrap(),
+            Instruction {
+                mv: 11,
+                from: 2,
+                to: 3
+            }
+        );
+        assert_eq!("2-8".parse::<Span>().unwrap(), Span(2, 8));
+        assert_eq!("{x}".parse::<Braced>().unwrap(), Braced('x'));
+    }
+
+    #[test]
+    fn test_field_error() {
+        let err = parse_err::<Instruction>("move 1 from two to 3");
+        assert_eq!((err.offset, err.len), (12, 3));
+        assert!(matches!(
+            err.kind,
+            ParseErrorKind::Field { name: "from", .. }
+        ));
+        assert_eq!(
+            err.to_string(),
+            r#"Instruction: field `from` could not parse "two" at column 13: invalid digit found in string"#
+        );
+        assert!(error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn test_literal_error() {
+        let err = parse_err::<Instruction>("move 1 frm 2 to 3");
+        assert!(matches!(
+            err.kind,
+            ParseErrorKind::Missing {
+                expected: " from ",
+                after: "mv"
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            r#"Instruction: expected " from " after field `mv` at column 6, found "1 frm 2 to 3""#
+        );
+
+        let err = parse_err::<Instruction>("shift 1 from 2 to 3");
+        assert_eq!(
+            err.to_string(),
+            r#"Instruction: expected "move " at column 1, found "shift 1 from 2 to 3""#
+        );
+    }
+
+    #[test]
+    fn test_trailing_error() {
+        let err = parse_err::<Braced>("{x}!");
+        assert!(matches!(err.kind, ParseErrorKind::Trailing));
+        assert_eq!(err.column(), 4);
+    }
+
+    #[test]
+    fn test_enum() {
+        assert_eq!("$ cd /".parse::<Line>().unwrap(), Line::Cd("/".into()));
+        assert_eq!("$ ls".parse::<Line>().unwrap(), Line::Ls);
+        assert_eq!(
+            "dir a".parse::<Line>().unwrap(),
+            Line::Dir { name: "a".into() }
+        );
+        assert_eq!(
+            "584 i".parse::<Line>().unwrap(),
+            Line::File(584, "i".into())
+        );
+    }
+
+    #[test]
+    fn test_enum_error() {
+        let err = parse_err::<Line>("big b.txt");
+        assert_eq!(
+            err.to_string(),
+            r#"Line::File: field `0` could not parse "big" at column 1: invalid digit found in string"#
+        );
+
+        let err = parse_err::<Line>("nonsense");
+        assert!(matches!(err.kind, ParseErrorKind::NoMatch { .. }));
+        assert_eq!(
+            err.to_string(),
+            r#"Line: "nonsense" did not match any of ["$ cd {0}", "$ ls", "dir {name}", "{0} {1}"]"#
+        );
+    }
+
+    #[test]
+    fn test_ints() {
+        let line = "Sensor at x=2, y=-18: closest beacon is at x=-2, y=15";
+        assert_eq!(ints::<i32>(line).unwrap(), [2, -18, -2, 15]);
+        assert_eq!(ints::<u32>(line).unwrap(), [2, 18, 2, 15]);
+        assert_eq!(ints::<i64>("2-8,-3--7").unwrap(), [2, 8, -3, -7]);
+        assert_eq!(ints::<i8>("-1 x-2 -x3").unwrap(), [-1, 2, 3]);
+        assert!(ints::<usize>("no numbers").unwrap().is_empty());
+        assert_eq!(
+            ints::<usize>("Blueprint 1: Each ore robot costs 4 ore.").unwrap(),
+            [1, 4]
+        );
+    }
+
+    #[test]
+    fn test_ints_n() {
+        assert_eq!(ints_n::<usize, 3>("move 1 from 2 to 1").unwrap(), [1, 2, 1]);
+        assert_eq!(ints_n::<i64, 0>("").unwrap(), []);
+
+        let err = ints_n::<u8, 2>("1 2 3 4").unwrap_err();
+        assert!(matches!(
+            err.kind,
+            ParseErrorKind::Count {
+                expected: 2,
+                found: 4
+            }
+        ));
+        assert_eq!((err.offset, err.len), (0, 7));
+    }
+
+    #[test]
+    fn test_ints_overflow() {
+        let err = ints_n::<u8, 2>("x=12, y=300").unwrap_err();
+        assert_eq!((err.offset, err.len), (8, 3));
+        assert_eq!(
+            err.to_string(),
+            r#"ints: field `u8` could not parse "300" at column 9: number too large to fit in target type"#
+        );
+        let err = ints::<i8>("at -129").unwrap_err();
+        assert_eq!((err.offset, err.len), (3, 4));
+    }
+}
@@ -0,0 +1,456 @@
+//! Inclusive integer intervals and sets of them.
+//!
+//! ```rust
+//! use aoc::ranges::{Interval, RangeSet};
+//!
+//! let a: Interval<i32> = "2-8".parse().unwrap();
+//! let b = Interval::new(3, 7).unwrap();
+//! assert!(a.contains_interval(&b));
+//! assert_eq!(a.intersection(&b), Some(b));
+//!
+//! let mut covered: RangeSet<i32> = [a, Interval::new(12, 14).unwrap()].into_iter().collect();
+//! covered.remove(Interval::new(5, 5).unwrap());
+//! assert_eq!(covered.len(), 6 + 3);
+//! ```
+use std::{
+    fmt::{self, Debug, Display},
+    ops::RangeInclusive,
+    str::FromStr,
+};
+
+use crate::{err, Error, Result};
+
+/// Integer types usable as [`Interval`] bounds.
+pub trait Discrete: Copy + Ord + Debug {
+    /// The next value, or `None` at the maximum.
+    fn succ(self) -> Option<Self>;
+    /// The previous value, or `None` at the minimum.
+    fn pred(self) -> Option<Self>;
+    /// The number of values in `start..=end`, saturating at `u128::MAX`.
+    fn count(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_discrete {
+    ($($ty:ty),*) => {
+        $(
+            impl Discrete for $ty {
+                fn succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                #[allow(clippy::cast_lossless, trivial_numeric_casts)]
+                fn count(start: Self, end: Self) -> u128 {
+                    (end.abs_diff(start) as u128).saturating_add(1)
+                }
+            }
+        )*
+    };
+}
+
+impl_discrete!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// A non-empty inclusive interval `start..=end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: Discrete> Interval<T> {
+    /// Create `start..=end`, failing if `start > end`.
+    pub fn new(start: T, end: T) -> Result<Self> {
+        if start > end {
+            return Err(err!("interval start {:?} is after end {:?}", start, end));
+        }
+        Ok(Self { start, end })
+    }
+
+    /// The interval containing only `value`.
+    pub fn single(value: T) -> Self {
+        Self {
+            start: value,
+            end: value,
+        }
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// The number of values in the interval.
+    pub fn len(&self) -> u128 {
+        T::count(self.start, self.end)
+    }
+
+    /// Always `false`, as intervals hold at least one value; present to pair
+    /// with [`Interval::len`].
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether `other` lies entirely within `self`.
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether the intervals share at least one value.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Whether the intervals overlap or sit immediately next to each other,
+    /// such that their union is a single interval.
+    pub fn touches(&self, other: &Self) -> bool {
+        self.overlaps(other)
+            || self.end.succ() == Some(other.start)
+            || other.end.succ() == Some(self.start)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other).then(|| Self {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        })
+    }
+
+    /// The single interval covering both, if they [touch](Interval::touches).
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        self.touches(other).then(|| self.hull(other))
+    }
+
+    /// The smallest interval covering both, including any gap between them.
+    pub fn hull(&self, other: &Self) -> Self {
+        Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
+    /// The parts of `self` not covered by `other`: none, one or two
+    /// intervals.
+    pub fn difference(&self, other: &Self) -> impl Iterator<Item = Self> {
+        let (mut before, mut after) = (None, None);
+        if !self.overlaps(other) {
+            before = Some(*self);
+        } else {
+            if self.start < other.start {
+                before = other.start.pred().map(|end| Self {
+                    start: self.start,
+                    end,
+                });
+            }
+            if other.end < self.end {
+                after = other.end.succ().map(|start| Self {
+                    start,
+                    end: self.end,
+                });
+            }
+        }
+        before.into_iter().chain(after)
+    }
+
+    /// Iterate over every value in the interval.
+    pub fn iter(&self) -> impl Iterator<Item = T> {
+        let end = self.end;
+        std::iter::successors(Some(self.start), move |&value| {
+            (value < end).then(|| value.succ()).flatten()
+        })
+    }
+}
+
+impl<T: Discrete> From<Interval<T>> for RangeInclusive<T> {
+    fn from(interval: Interval<T>) -> Self {
+        interval.start..=interval.end
+    }
+}
+
+impl<T: Discrete> TryFrom<RangeInclusive<T>> for Interval<T> {
+    type Error = Error;
+
+    fn try_from(range: RangeInclusive<T>) -> Result<Self> {
+        let (start, end) = range.into_inner();
+        Self::new(start, end)
+    }
+}
+
+/// Parse `start-end`, as in `2-8` or `-3--1`.
+impl<T> FromStr for Interval<T>
+where
+    T: Discrete + FromStr,
+    T::Err: Into<Error>,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        // Skip the first character so a leading minus sign isn't taken as the
+        // separator
+        let sep = s
+            .char_indices()
+            .skip(1)
+            .find_map(|(idx, c)| (c == '-').then_some(idx))
+            .ok_or_else(|| err!("expected `start-end`, found {:?}", s))?;
+        let parse = |part: &str| {
+            part.parse::<T>()
+                .map_err(|e| err!("invalid bound {:?} in {:?}: {}", part, s, e.into()))
+        };
+        Self::new(parse(&s[..sep])?, parse(&s[sep + 1..])?)
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// A set of values stored as sorted, disjoint, non-adjacent intervals.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Discrete> RangeSet<T> {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Add every value in `interval`, merging with any intervals it touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        // First interval that could touch the new one
+        let lo = self
+            .intervals
+            .partition_point(|i| i.end < interval.start && !i.touches(&interval));
+        // One past the last interval that touches the new one
+        let hi = lo
+            + self.intervals[lo..]
+                .iter()
+                .take_while(|i| i.touches(&interval))
+                .count();
+
+        let merged = self.intervals[lo..hi]
+            .iter()
+            .fold(interval, |acc, i| acc.hull(i));
+        self.intervals.splice(lo..hi, [merged]);
+    }
+
+    /// Remove every value in `interval`.
+    pub fn remove(&mut self, interval: Interval<T>) {
+        let lo = self.intervals.partition_point(|i| i.end < interval.start);
+        let hi = lo
+            + self.intervals[lo..]
+                .iter()
+                .take_while(|i| i.start <= interval.end)
+                .count();
+
+        let remaining: Vec<_> = self.intervals[lo..hi]
+            .iter()
+            .flat_map(|i| i.difference(&interval))
+            .collect();
+        self.intervals.splice(lo..hi, remaining);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.intervals.partition_point(|i| i.end < value);
+        self.intervals
+            .get(idx)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    /// The total number of values covered.
+    pub fn len(&self) -> u128 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of values in `bounds` that the set covers.
+    pub fn count_within(&self, bounds: &Interval<T>) -> u128 {
+        self.intervals
+            .iter()
+            .filter_map(|i| i.intersection(bounds))
+            .map(|i| i.len())
+            .sum()
+    }
+
+    /// The values in `bounds` that the set does *not* cover.
+    pub fn gaps_within(&self, bounds: &Interval<T>) -> Self {
+        let mut gaps = Self::new();
+        gaps.insert(*bounds);
+        gaps.subtract(self);
+        gaps
+    }
+
+    /// Add every value in `other`.
+    pub fn extend_from(&mut self, other: &Self) {
+        for interval in &other.intervals {
+            self.insert(*interval);
+        }
+    }
+
+    /// Remove every value in `other`.
+    pub fn subtract(&mut self, other: &Self) {
+        for interval in &other.intervals {
+            self.remove(*interval);
+        }
+    }
+
+    /// The sorted, disjoint intervals making up the set.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    /// Iterate over every value in the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        self.intervals.iter().flat_map(Interval::iter)
+    }
+}
+
+impl<T: Discrete> FromIterator<Interval<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Discrete> Extend<Interval<T>> for RangeSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iv(start: i32, end: i32) -> Interval<i32> {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn test_interval() {
+        assert!(Interval::new(3, 2).is_err());
+        assert_eq!(iv(2, 8).len(), 7);
+        assert_eq!(Interval::new(i8::MIN, i8::MAX).unwrap().len(), 256);
+        assert_eq!(Interval::new(0, u128::MAX).unwrap().len(), u128::MAX);
+        assert!(iv(2, 8).contains(2) && iv(2, 8).contains(8));
+        assert!(!iv(2, 8).contains(9));
+        assert!(iv(2, 8).contains_interval(&iv(3, 7)));
+        assert!(!iv(3, 7).contains_interval(&iv(2, 8)));
+        assert_eq!(iv(2, 4).iter().collect::<Vec<_>>(), [2, 3, 4]);
+        assert_eq!(
+            Interval::single(u8::MAX).iter().collect::<Vec<_>>(),
+            [u8::MAX]
+        );
+    }
+
+    #[test]
+    fn test_overlap() {
+        assert!(iv(5, 7).overlaps(&iv(7, 9)));
+        assert!(!iv(2, 4).overlaps(&iv(6, 8)));
+        assert!(!iv(2, 4).overlaps(&iv(5, 8)));
+        assert!(iv(2, 4).touches(&iv(5, 8)));
+
+        assert_eq!(iv(2, 6).intersection(&iv(4, 8)), Some(iv(4, 6)));
+        assert_eq!(iv(2, 4).intersection(&iv(6, 8)), None);
+        assert_eq!(iv(2, 4).union(&iv(5, 8)), Some(iv(2, 8)));
+        assert_eq!(iv(2, 4).union(&iv(6, 8)), None);
+        assert_eq!(iv(2, 4).hull(&iv(6, 8)), iv(2, 8));
+    }
+
+    #[test]
+    fn test_difference() {
+        let diff = |a: Interval<i32>, b| a.difference(&b).collect::<Vec<_>>();
+        assert_eq!(diff(iv(2, 8), iv(4, 5)), [iv(2, 3), iv(6, 8)]);
+        assert_eq!(diff(iv(2, 8), iv(0, 5)), [iv(6, 8)]);
+        assert_eq!(diff(iv(2, 8), iv(0, 10)), []);
+        assert_eq!(diff(iv(2, 8), iv(10, 12)), [iv(2, 8)]);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("2-8".parse::<Interval<i32>>().unwrap(), iv(2, 8));
+        assert_eq!("-3--1".parse::<Interval<i32>>().unwrap(), iv(-3, -1));
+        assert_eq!("-3-1".parse::<Interval<i32>>().unwrap(), iv(-3, 1));
+        assert!("8-2".parse::<Interval<i32>>().is_err());
+        assert!("8".parse::<Interval<i32>>().is_err());
+        assert!("a-2".parse::<Interval<i32>>().is_err());
+        assert_eq!(iv(-3, 1).to_string(), "-3-1");
+    }
+
+    #[test]
+    fn test_rangeset_insert() {
+        let mut set = RangeSet::new();
+        set.insert(iv(10, 12));
+        set.insert(iv(1, 2));
+        set.insert(iv(5, 6));
+        assert_eq!(set.intervals(), [iv(1, 2), iv(5, 6), iv(10, 12)]);
+
+        // Adjacent intervals merge
+        set.insert(iv(3, 4));
+        assert_eq!(set.intervals(), [iv(1, 6), iv(10, 12)]);
+
+        set.insert(iv(0, 20));
+        assert_eq!(set.intervals(), [iv(0, 20)]);
+        assert_eq!(set.len(), 21);
+    }
+
+    #[test]
+    fn test_rangeset_remove() {
+        let mut set: RangeSet<i32> = [iv(0, 10), iv(20, 30)].into_iter().collect();
+        set.remove(iv(5, 25));
+        assert_eq!(set.intervals(), [iv(0, 4), iv(26, 30)]);
+        set.remove(iv(0, 0));
+        set.remove(iv(30, 40));
+        assert_eq!(set.intervals(), [iv(1, 4), iv(26, 29)]);
+        assert!(set.contains(4) && !set.contains(5) && set.contains(26));
+
+        let other: RangeSet<i32> = [iv(2, 2), iv(27, 28)].into_iter().collect();
+        set.subtract(&other);
+        assert_eq!(
+            set.intervals(),
+            [iv(1, 1), iv(3, 4), iv(26, 26), iv(29, 29)]
+        );
+        assert_eq!(set.iter().collect::<Vec<_>>(), [1, 3, 4, 26, 29]);
+    }
+
+    #[test]
+    fn test_rangeset_coverage() {
+        // Day 15 example, row y=10
+        let set: RangeSet<i32> = [
+            iv(12, 12),
+            iv(2, 14),
+            iv(2, 2),
+            iv(-2, 2),
+            iv(16, 24),
+            iv(14, 18),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.intervals(), [iv(-2, 24)]);
+        assert_eq!(set.len(), 27);
+        assert_eq!(set.count_within(&iv(0, 20)), 21);
+
+        let set: RangeSet<i32> = [iv(-3, 13), iv(15, 25)].into_iter().collect();
+        assert_eq!(set.gaps_within(&iv(0, 20)).intervals(), [iv(14, 14)]);
+        assert!(set.gaps_within(&iv(0, 13)).is_empty());
+    }
+}
@@ -0,0 +1,698 @@
+//! A common shape for each day's solution, so that every day binary can be
+//! timed and recorded the same way by the `aoc` runner.
+//!
+//! A day implements [`Solution`] and hands it to [`run`] from `main`:
+//!
+//! ```rust
+//! use aoc::{solution::Solution, Result};
+//!
+//! struct Day00;
+//!
+//! impl Solution for Day00 {
+//!     const YEAR: u16 = 2022;
+//!     const DAY: u8 = 0;
+//!     type Parsed = Vec<u32>;
+//!     type Params = ();
+//!     type Answer1 = u32;
+//!     type Answer2 = u32;
+//!
+//!     fn parse(input: &str) -> Result<Self::Parsed> {
+//!         input.lines().map(|line| Ok(line.parse()?)).collect()
+//!     }
+//!
+//!     fn part1(parsed: &Self::Parsed, (): &()) -> Result<u32> {
+//!         Ok(parsed.iter().sum())
+//!     }
+//!
+//!     fn part2(parsed: &Self::Parsed, (): &()) -> Result<u32> {
+//!         Ok(parsed.iter().product())
+//!     }
+//! }
+//!
+//! let reports = aoc::solution::solve::<Day00>("2\n3\n4").unwrap();
+//! assert_eq!(reports[1].answer.as_deref(), Some("9"));
+//! assert_eq!(reports[2].answer.as_deref(), Some("24"));
+//! ```
+//!
+//! Run directly, a day binary prints `2022 day 05 part 1: ...`. Run with
+//! `--protocol` (as the runner does), it prints one [`Record`] per line
+//! instead, so answers and timings can be read back reliably.
+//!
+//! Constants that differ between the examples and the real input, like the
+//! row to scan or how many elves to count, are the day's [`Params`], declared
+//! with [`params!`](crate::params). Their defaults are the real input's; an
+//! example in [`aoc_tests!`](crate::aoc_tests) overrides them, and so does
+//! `--param name=value` on the command line.
+use std::{
+    fmt::{self, Display},
+    path::Path,
+    result,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    diagnostic::Diagnostic,
+    err,
+    store::Puzzle,
+    trace::{self, Event},
+    Error, Result,
+};
+
+/// One day's puzzle: parse the input once, then solve each part from it.
+pub trait Solution {
+    const YEAR: u16;
+    /// Day of the month, 1 through 25.
+    const DAY: u8;
+    type Parsed;
+    /// The constants each part is solved with; `()` for a day without any.
+    type Params: Params;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed, params: &Self::Params) -> Result<Self::Answer1>;
+    fn part2(parsed: &Self::Parsed, params: &Self::Params) -> Result<Self::Answer2>;
+}
+
+/// A day's puzzle parameters, defaulting to the real input's. Declare them
+/// with [`params!`](crate::params).
+pub trait Params: Default {
+    /// Set the parameter `name` from `value`, as given by `--param
+    /// name=value`.
+    fn set(&mut self, name: &str, value: &str) -> Result<()>;
+
+    /// The defaults with each `name=value` in `overrides` applied in turn.
+    fn with_overrides<'a>(overrides: impl IntoIterator<Item = &'a str>) -> Result<Self> {
+        let mut params = Self::default();
+        for assignment in overrides {
+            let (name, value) = assignment
+                .split_once('=')
+                .ok_or_else(|| err!("expected a parameter as NAME=VALUE: {:?}", assignment))?;
+            params.set(name.trim(), value.trim())?;
+        }
+        Ok(params)
+    }
+}
+
+impl Params for () {
+    fn set(&mut self, name: &str, _: &str) -> Result<()> {
+        Err(err!("unknown parameter {:?}: this day has none", name))
+    }
+}
+
+/// Declare a struct of puzzle parameters with their defaults, implementing
+/// [`Params`] for it. Each field is public and can
+/// be set by name with `--param`.
+///
+/// ```rust
+/// aoc::params! {
+///     /// Where to look for beacons.
+///     pub struct Scan {
+///         /// The row to count positions in.
+///         row: i64 = 2_000_000,
+///         limit: i64 = 4_000_000,
+///     }
+/// }
+///
+/// use aoc::solution::Params;
+///
+/// let mut scan = Scan::default();
+/// assert_eq!(scan.row, 2_000_000);
+/// scan.set("row", "10").unwrap();
+/// assert_eq!(scan, Scan { row: 10, limit: 4_000_000 });
+/// assert!(scan.set("row", "ten").is_err());
+/// assert!(scan.set("column", "3").is_err());
+/// assert_eq!(Scan::with_overrides(["limit=20"]).unwrap().limit, 20);
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_meta:meta])* $field:ident: $ty:ty = $default:expr),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq)]
+        $vis struct $name {
+            $($(#[$field_meta])* pub $field: $ty,)+
+        }
+
+        impl ::std::default::Default for $name {
+            fn default() -> Self {
+                Self {
+                    $($field: $default,)+
+                }
+            }
+        }
+
+        impl $crate::solution::Params for $name {
+            fn set(&mut self, name: &str, value: &str) -> $crate::Result<()> {
+                match name {
+                    $(::std::stringify!($field) => {
+                        self.$field = value.parse::<$ty>().map_err(|e| {
+                            $crate::err!("bad value for parameter {}: {:?}: {}", name, value, e)
+                        })?;
+                    })+
+                    _ => {
+                        return Err($crate::err!(
+                            "unknown parameter {:?}: expected one of {}",
+                            name,
+                            [$(::std::stringify!($field)),+].join(", ")
+                        ))
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+/// A timed step of a solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [Stage::Parse, Stage::Part1, Stage::Part2];
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "1",
+            Stage::Part2 => "2",
+        })
+    }
+}
+
+impl FromStr for Stage {
+    type Err = Error;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Stage::Parse),
+            "1" => Ok(Stage::Part1),
+            "2" => Ok(Stage::Part2),
+            _ => Err(err!("unknown stage: {:?}", s)),
+        }
+    }
+}
+
+/// One line of the output protocol between a day binary and the runner.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Record {
+    /// `answer <stage> <answer>`; the answer runs to the end of the line.
+    Answer { stage: Stage, answer: String },
+    /// `time <stage> <nanoseconds>`
+    Time { stage: Stage, nanos: u128 },
+    /// `trace <stage> <json>`, one [`trace`](mod@crate::trace) event as JSON.
+    Trace { stage: Stage, event: String },
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Record::Answer { stage, answer } => write!(f, "answer {stage} {answer}"),
+            Record::Time { stage, nanos } => write!(f, "time {stage} {nanos}"),
+            Record::Trace { stage, event } => write!(f, "trace {stage} {event}"),
+        }
+    }
+}
+
+impl FromStr for Record {
+    type Err = Error;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let mut words = s.splitn(3, ' ');
+        let (Some(kind), Some(stage), Some(rest)) = (words.next(), words.next(), words.next())
+        else {
+            return Err(err!("incomplete record: {:?}", s));
+        };
+        let stage = stage.parse()?;
+        match kind {
+            "answer" => Ok(Record::Answer {
+                stage,
+                answer: rest.to_string(),
+            }),
+            "time" => Ok(Record::Time {
+                stage,
+                nanos: rest.parse()?,
+            }),
+            "trace" => Ok(Record::Trace {
+                stage,
+                event: rest.to_string(),
+            }),
+            _ => Err(err!("unknown record: {:?}", s)),
+        }
+    }
+}
+
+/// The outcome of one [`Stage`]. `answer` is `None` for [`Stage::Parse`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    pub stage: Stage,
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+    /// The JSON of each event traced during the stage, if tracing.
+    pub trace: Vec<String>,
+}
+
+impl Report {
+    /// The protocol lines for this report.
+    #[must_use]
+    pub fn records(&self) -> Vec<Record> {
+        let mut records = Vec::new();
+        if let Some(answer) = &self.answer {
+            records.push(Record::Answer {
+                stage: self.stage,
+                answer: answer.clone(),
+            });
+        }
+        records.push(Record::Time {
+            stage: self.stage,
+            nanos: self.elapsed.as_nanos(),
+        });
+        records.extend(self.trace.iter().map(|event| Record::Trace {
+            stage: self.stage,
+            event: event.clone(),
+        }));
+        records
+    }
+}
+
+/// Run one stage, timing it and keeping the events it traces.
+fn timed<T>(f: impl FnOnce() -> Result<T>) -> Result<(T, Duration, Vec<String>)> {
+    let ((value, elapsed), events) = trace::collect(|| {
+        let start = Instant::now();
+        let value = f();
+        (value, start.elapsed())
+    });
+    Ok((value?, elapsed, events.iter().map(Event::to_json).collect()))
+}
+
+/// Parse `input` and solve both parts with the default parameters, timing
+/// each stage.
+pub fn solve<S: Solution>(input: &str) -> Result<Vec<Report>> {
+    solve_part::<S>(input, &S::Params::default(), None)
+}
+
+/// Parse `input` and solve only `part`, or both parts if it's `None`.
+pub fn solve_part<S: Solution>(
+    input: &str,
+    params: &S::Params,
+    part: Option<Stage>,
+) -> Result<Vec<Report>> {
+    if part == Some(Stage::Parse) {
+        return Err(err!("only parts 1 and 2 can be solved on their own"));
+    }
+    let (parsed, elapsed, trace) = timed(|| S::parse(input))?;
+    let mut reports = vec![Report {
+        stage: Stage::Parse,
+        answer: None,
+        elapsed,
+        trace,
+    }];
+
+    if part.is_none_or(|part| part == Stage::Part1) {
+        let (answer, elapsed, trace) = timed(|| S::part1(&parsed, params))?;
+        reports.push(Report {
+            stage: Stage::Part1,
+            answer: Some(answer.to_string()),
+            elapsed,
+            trace,
+        });
+    }
+
+    if part.is_none_or(|part| part == Stage::Part2) {
+        let (answer, elapsed, trace) = timed(|| S::part2(&parsed, params))?;
+        reports.push(Report {
+            stage: Stage::Part2,
+            answer: Some(answer.to_string()),
+            elapsed,
+            trace,
+        });
+    }
+    Ok(reports)
+}
+
+/// The `main` of a day binary: solve `input` and print the answers, or the
+/// protocol records when run with `--protocol`. With `--trace`, the events
+/// each stage traces are printed too (to stderr, without `--protocol`),
+/// `--part 1` or `--part 2` solves only that part, and each `--param
+/// name=value` overrides one of the day's [`Params`].
+///
+/// An error that is a [`Diagnostic`] is shown against the day's input file,
+/// with the line it points at.
+pub fn run<S: Solution>(input: &str) -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let protocol = args.iter().any(|arg| arg == "--protocol");
+    if args.iter().any(|arg| arg == "--trace") {
+        trace::enable()?;
+    }
+    let part = match args.iter().position(|arg| arg == "--part") {
+        Some(idx) => Some(
+            args.get(idx + 1)
+                .ok_or_else(|| err!("--part needs 1 or 2"))?
+                .parse()?,
+        ),
+        None => None,
+    };
+    let overrides = args
+        .iter()
+        .enumerate()
+        .filter(|(_, arg)| *arg == "--param")
+        .map(|(idx, _)| {
+            args.get(idx + 1)
+                .map(String::as_str)
+                .ok_or_else(|| err!("--param needs NAME=VALUE"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let params = S::Params::with_overrides(overrides)?;
+    let reports = solve_part::<S>(input, &params, part).map_err(|e| {
+        match e.downcast_ref::<Diagnostic>() {
+            Some(diagnostic) => {
+                let path = Puzzle::new(S::YEAR, S::DAY).map_or_else(
+                    |_| "input.txt".to_string(),
+                    |puzzle| puzzle.input_path(Path::new("")).display().to_string(),
+                );
+                eprintln!("{}", diagnostic.render(&path));
+                err!("could not solve {}", path)
+            }
+            None => e,
+        }
+    })?;
+    for report in reports {
+        if protocol {
+            for record in report.records() {
+                println!("{record}");
+            }
+            continue;
+        }
+        for event in &report.trace {
+            eprintln!("{} day {:02} {}: {}", S::YEAR, S::DAY, report.stage, event);
+        }
+        if let Some(answer) = report.answer {
+            println!(
+                "{} day {:02} part {}: {}",
+                S::YEAR,
+                S::DAY,
+                report.stage,
+                answer
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Generate tests for a [`Solution`] from inputs and their expected answers.
+///
+/// Each case becomes a module of `#[test]`s named after it: `parse` checks
+/// that the input parses, and `part1` and `part2` compare the answer's
+/// `Display` output with the expected value. Use `_` for an answer that isn't
+/// known yet to skip that part, and `stored` to check the answer against the
+/// digest in the workspace's `answers.tsv` (see
+/// [`Answers`](crate::store::Answers)) so that the answers to real inputs
+/// aren't spelled out in the source. Attributes before a case apply to each of
+/// its tests, e.g. `#[ignore = "slow"]` for a real input that takes a while.
+/// A case solves with the day's default [`Params`] unless it overrides some
+/// after its input, as `example: EXAMPLE, row = 10 => (26, _)`.
+///
+/// ```rust
+/// # use aoc::{solution::Solution, Result};
+/// # aoc::params! { struct Scale { factor: usize = 2 } }
+/// # struct Day06;
+/// # impl Solution for Day06 {
+/// #     const YEAR: u16 = 2022;
+/// #     const DAY: u8 = 6;
+/// #     type Parsed = String;
+/// #     type Params = Scale;
+/// #     type Answer1 = usize;
+/// #     type Answer2 = usize;
+/// #     fn parse(input: &str) -> Result<String> { Ok(input.to_string()) }
+/// #     fn part1(input: &String, _: &Scale) -> Result<usize> { Ok(input.len()) }
+/// #     fn part2(input: &String, scale: &Scale) -> Result<usize> { Ok(input.len() * scale.factor) }
+/// # }
+/// # const INPUT: &str = "abcd";
+/// # fn main() {}
+/// aoc::aoc_tests! {
+///     Day06;
+///     // Generates `example::parse`, `example::part1` and `example::part2`
+///     example: "abc" => (3, 6),
+///     // Part 2 isn't solved yet, so only `short::parse` and `short::part1`
+///     short: "ab" => (2, _),
+///     // Solved with `Scale { factor: 3 }`
+///     tripled: "ab", factor = 3 => (2, 6),
+///     #[ignore = "slow"]
+///     long: INPUT => (4, 8),
+/// }
+/// ```
+#[macro_export]
+macro_rules! aoc_tests {
+    (
+        $solution:ty;
+        $(
+            $(#[$meta:meta])*
+            $name:ident: $input:expr $(, $param:ident = $value:expr)* => ($part1:tt, $part2:tt)
+        ),+ $(,)?
+    ) => {
+        $(
+            mod $name {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #[allow(dead_code)]
+                fn params() -> <$solution as $crate::solution::Solution>::Params {
+                    #[allow(unused_mut)]
+                    let mut params = <<$solution as $crate::solution::Solution>::Params as ::std::default::Default>::default();
+                    $(params.$param = $value;)*
+                    params
+                }
+
+                $(#[$meta])*
+                #[test]
+                fn parse() {
+                    <$solution as $crate::solution::Solution>::parse($input).unwrap();
+                }
+
+                $crate::aoc_tests!(@part $solution, $input, part1, $part1, $(#[$meta])*);
+                $crate::aoc_tests!(@part $solution, $input, part2, $part2, $(#[$meta])*);
+            }
+        )+
+    };
+    (@part $solution:ty, $input:expr, $part:ident, _, $(#[$meta:meta])*) => {};
+    (@part $solution:ty, $input:expr, $part:ident, stored, $(#[$meta:meta])*) => {
+        $(#[$meta])*
+        #[test]
+        fn $part() {
+            use $crate::solution::Solution;
+            let parsed = <$solution>::parse($input).unwrap();
+            let answer = <$solution>::$part(&parsed, &params()).unwrap();
+            $crate::store::assert_stored(
+                ::std::env!("CARGO_MANIFEST_DIR"),
+                $crate::store::Puzzle::new(<$solution>::YEAR, <$solution>::DAY).unwrap(),
+                $crate::aoc_tests!(@stage $part),
+                &answer.to_string(),
+            );
+        }
+    };
+    (@part $solution:ty, $input:expr, $part:ident, $expected:tt, $(#[$meta:meta])*) => {
+        $(#[$meta])*
+        #[test]
+        fn $part() {
+            use $crate::solution::Solution;
+            let parsed = <$solution>::parse($input).unwrap();
+            let answer = <$solution>::$part(&parsed, &params()).unwrap();
+            assert_eq!(answer.to_string(), $expected.to_string());
+        }
+    };
+    (@stage part1) => {
+        $crate::solution::Stage::Part1
+    };
+    (@stage part2) => {
+        $crate::solution::Stage::Part2
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_roundtrip() {
+        for line in [
+            "answer 1 CMZ",
+            "answer 2 has spaces in it",
+            "time parse 1234",
+            "time 2 0",
+            r#"trace 1 {"event":"game","fields":{"score":8}}"#,
+        ] {
+            let record: Record = line.parse().unwrap();
+            assert_eq!(record.to_string(), line);
+        }
+        assert_eq!(
+            "answer 2 a b".parse::<Record>().unwrap(),
+            Record::Answer {
+                stage: Stage::Part2,
+                answer: "a b".into()
+            }
+        );
+    }
+
+    #[test]
+    fn test_report_records() {
+        let report = Report {
+            stage: Stage::Part1,
+            answer: Some("8".into()),
+            elapsed: Duration::from_nanos(42),
+            trace: vec![r#"{"event":"game","fields":{}}"#.into()],
+        };
+        let lines: Vec<_> = report.records().iter().map(Record::to_string).collect();
+        assert_eq!(
+            lines,
+            [
+                "answer 1 8",
+                "time 1 42",
+                r#"trace 1 {"event":"game","fields":{}}"#
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bad_records() {
+        for line in ["", "answer 1", "time 3 10", "time 1 ten", "elapsed 1 10"] {
+            assert!(line.parse::<Record>().is_err(), "{line:?}");
+        }
+    }
+
+    struct Failing;
+
+    impl Solution for Failing {
+        const YEAR: u16 = 2022;
+        const DAY: u8 = 0;
+        type Parsed = ();
+        type Params = ();
+        type Answer1 = u8;
+        type Answer2 = u8;
+
+        fn parse(_: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1((): &(), (): &()) -> Result<u8> {
+            Ok(1)
+        }
+
+        fn part2((): &(), (): &()) -> Result<u8> {
+            Err(err!("part 2 is unsolved"))
+        }
+    }
+
+    #[test]
+    fn test_solve_error() {
+        let err = solve::<Failing>("").unwrap_err();
+        assert_eq!(err.to_string(), "part 2 is unsolved");
+    }
+
+    #[test]
+    fn test_solve_part() {
+        let reports = solve_part::<Failing>("", &(), Some(Stage::Part1)).unwrap();
+        let stages: Vec<_> = reports.iter().map(|report| report.stage).collect();
+        assert_eq!(stages, [Stage::Parse, Stage::Part1]);
+        assert!(solve_part::<Failing>("", &(), Some(Stage::Part2)).is_err());
+        assert!(solve_part::<Failing>("", &(), Some(Stage::Parse)).is_err());
+    }
+
+    crate::params! {
+        struct Limits {
+            /// Only words at least this long count.
+            shortest: usize = 1,
+            suffix: String = String::new(),
+        }
+    }
+
+    struct Lengths;
+
+    impl Solution for Lengths {
+        const YEAR: u16 = 2022;
+        const DAY: u8 = 0;
+        type Parsed = Vec<String>;
+        type Params = Limits;
+        type Answer1 = usize;
+        type Answer2 = String;
+
+        fn parse(input: &str) -> Result<Self::Parsed> {
+            Ok(input.split_whitespace().map(String::from).collect())
+        }
+
+        fn part1(words: &Self::Parsed, limits: &Limits) -> Result<usize> {
+            Ok(words
+                .iter()
+                .filter(|word| word.len() >= limits.shortest)
+                .count())
+        }
+
+        fn part2(words: &Self::Parsed, limits: &Limits) -> Result<String> {
+            words
+                .iter()
+                .max()
+                .map(|word| format!("{word}{}", limits.suffix))
+                .ok_or_else(|| err!("no words"))
+        }
+    }
+
+    const WORDS: &str = "one two three";
+
+    aoc_tests! {
+        Lengths;
+        words: WORDS => (3, "two"),
+        blank: "" => (0, _),
+        #[ignore = "checks that attributes are passed through"]
+        ignored: "a b" => (2, "b"),
+        long_words: WORDS, shortest = 4 => (1, "two"),
+        both: "a bb", shortest = 2, suffix = "!".into() => (1, "bb!"),
+    }
+
+    #[test]
+    fn test_params() {
+        let limits = Limits::with_overrides(["shortest=5", "suffix = ?"]).unwrap();
+        assert_eq!(
+            limits,
+            Limits {
+                shortest: 5,
+                suffix: "?".into()
+            }
+        );
+        let reports = solve_part::<Lengths>(WORDS, &limits, None).unwrap();
+        assert_eq!(reports[1].answer.as_deref(), Some("1"));
+        assert_eq!(reports[2].answer.as_deref(), Some("two?"));
+
+        for (overrides, message) in [
+            (
+                &["shortest"][..],
+                "expected a parameter as NAME=VALUE: \"shortest\"",
+            ),
+            (
+                &["shortest=-1"],
+                "bad value for parameter shortest: \"-1\": invalid digit found in string",
+            ),
+            (
+                &["longest=3"],
+                "unknown parameter \"longest\": expected one of shortest, suffix",
+            ),
+        ] {
+            let err = Limits::with_overrides(overrides.iter().copied()).unwrap_err();
+            assert_eq!(err.to_string(), message);
+        }
+        assert_eq!(
+            <()>::with_overrides(["x=1"]).unwrap_err().to_string(),
+            "unknown parameter \"x\": this day has none"
+        );
+        assert!(<()>::with_overrides([]).is_ok());
+    }
+}
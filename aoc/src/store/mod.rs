@@ -0,0 +1,607 @@
+//! Where each puzzle's files live, keyed by year and day, and a store of
+//! known answers so that a rerun can tell whether an answer is still right.
+//!
+//! Each puzzle is a workspace member at `y<year>/d<day>`, e.g. `y2022/d05`,
+//! with the package name `y2022-d05` and its input at `y2022/d05/input.txt`.
+//!
+//! ```rust
+//! use aoc::{solution::Stage, store::{Answers, Puzzle, Revealed, Secret, Verdict}};
+//!
+//! let puzzle = Puzzle::new(2022, 5).unwrap();
+//! assert_eq!(puzzle.package(), "y2022-d05");
+//! assert_eq!(Puzzle::from_member("y2022/d05"), Some(puzzle));
+//!
+//! let mut answers = Answers::new(Some(Secret::new("hunter2")));
+//! answers.insert(puzzle, Stage::Part1, "CMZ").unwrap();
+//! assert_eq!(answers.check(puzzle, Stage::Part1, "CMZ"), Verdict::Correct);
+//! assert_eq!(answers.check(puzzle, Stage::Part2, "MCD"), Verdict::Unknown);
+//! assert_eq!(
+//!     answers.reveal(puzzle, Stage::Part1),
+//!     Some(Revealed::Answer("CMZ".into()))
+//! );
+//! ```
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::{err, solution::Stage, Result};
+
+/// One day of one year's Advent of Code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+}
+
+impl Puzzle {
+    /// The first Advent of Code was in 2015, and every year runs days 1
+    /// through 25.
+    pub fn new(year: u16, day: u8) -> Result<Self> {
+        if year < 2015 {
+            return Err(err!("there was no Advent of Code in {}", year));
+        }
+        if !(1..=25).contains(&day) {
+            return Err(err!("day must be between 1 and 25, not {}", day));
+        }
+        Ok(Self { year, day })
+    }
+
+    /// The puzzle at a workspace member path like `y2022/d05`.
+    #[must_use]
+    pub fn from_member(member: &str) -> Option<Self> {
+        let (year, day) = member.split_once('/')?;
+        Self::new(
+            year.strip_prefix('y')?.parse().ok()?,
+            day.strip_prefix('d')?.parse().ok()?,
+        )
+        .ok()
+    }
+
+    /// The workspace member path, e.g. `y2022/d05`.
+    #[must_use]
+    pub fn member(&self) -> String {
+        format!("y{}/d{:02}", self.year, self.day)
+    }
+
+    /// The package and binary name, e.g. `y2022-d05`.
+    #[must_use]
+    pub fn package(&self) -> String {
+        format!("y{}-d{:02}", self.year, self.day)
+    }
+
+    /// The puzzle input within the workspace at `root`.
+    #[must_use]
+    pub fn input_path(&self, root: &Path) -> PathBuf {
+        root.join(self.member()).join("input.txt")
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {:02}", self.year, self.day)
+    }
+}
+
+/// The result of comparing an answer with the store.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        /// The stored answer, if the store has the secret it was sealed with.
+        expected: Option<String>,
+    },
+    /// No answer is stored for this part yet.
+    Unknown,
+}
+
+/// Where an answer stands when revealing the store.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Revealed {
+    Answer(String),
+    /// Stored without a secret, so only its digest is known.
+    Unsealed,
+    /// Sealed with a different secret than this store's.
+    OtherSecret,
+}
+
+/// A local key for sealing answers so they can be revealed later, read from
+/// `AOC_SECRET` or a file that isn't checked in.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(Vec<u8>);
+
+impl Secret {
+    pub const ENV: &'static str = "AOC_SECRET";
+
+    #[must_use]
+    pub fn new(secret: &str) -> Self {
+        Self(secret.trim().as_bytes().to_vec())
+    }
+
+    /// The secret in `AOC_SECRET`, else in the file at `path`, or `None` if
+    /// there's neither.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if let Ok(secret) = std::env::var(Self::ENV) {
+            return Ok(Some(Self::new(&secret)));
+        }
+        match fs::read_to_string(path) {
+            Ok(secret) => Ok(Some(Self::new(&secret))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(err!("reading {}: {}", path.display(), e)),
+        }
+    }
+
+    /// Generate a new secret and write it to `path`, readable only by its
+    /// owner.
+    pub fn create(path: &Path) -> Result<Self> {
+        let secret = to_hex(&random::<32>()?);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        io::Write::write_all(&mut options.open(path)?, format!("{secret}\n").as_bytes())?;
+        Ok(Self::new(&secret))
+    }
+
+    /// `len` bytes to XOR with an answer, different for every salt.
+    fn keystream(&self, salt: &[u8], len: usize) -> Vec<u8> {
+        (0_u32..)
+            .flat_map(|block| {
+                Sha256::new()
+                    .chain_update(&self.0)
+                    .chain_update(salt)
+                    .chain_update(block.to_le_bytes())
+                    .finalize()
+            })
+            .take(len)
+            .collect()
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(..)")
+    }
+}
+
+fn random<const N: usize>() -> Result<[u8; N]> {
+    let mut bytes = [0; N];
+    getrandom::getrandom(&mut bytes).map_err(|e| err!("no randomness: {}", e))?;
+    Ok(bytes)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return Err(err!("not hex: {:?}", hex));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|idx| Ok(u8::from_str_radix(&hex[idx..idx + 2], 16)?))
+        .collect()
+}
+
+/// One stored answer: a salted digest to check answers against, and the
+/// answer itself sealed with the store's secret, if it had one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Entry {
+    salt: Vec<u8>,
+    digest: Vec<u8>,
+    sealed: Option<Vec<u8>>,
+}
+
+impl Entry {
+    fn new(answer: &str, secret: Option<&Secret>) -> Result<Self> {
+        let salt = random::<16>()?.to_vec();
+        let sealed = secret.map(|secret| {
+            let keystream = secret.keystream(&salt, answer.len());
+            answer.bytes().zip(keystream).map(|(a, k)| a ^ k).collect()
+        });
+        Ok(Self {
+            digest: digest(&salt, answer),
+            salt,
+            sealed,
+        })
+    }
+
+    fn matches(&self, answer: &str) -> bool {
+        digest(&self.salt, answer) == self.digest
+    }
+
+    fn reveal(&self, secret: &Secret) -> Revealed {
+        let Some(sealed) = &self.sealed else {
+            return Revealed::Unsealed;
+        };
+        let keystream = secret.keystream(&self.salt, sealed.len());
+        let bytes = sealed.iter().zip(keystream).map(|(s, k)| s ^ k).collect();
+        match String::from_utf8(bytes) {
+            Ok(answer) if self.matches(&answer) => Revealed::Answer(answer),
+            _ => Revealed::OtherSecret,
+        }
+    }
+}
+
+fn digest(salt: &[u8], answer: &str) -> Vec<u8> {
+    Sha256::new()
+        .chain_update(salt)
+        .chain_update(answer)
+        .finalize()
+        .to_vec()
+}
+
+const HEADER: &str = "year\tday\tpart\tsalt\tdigest\tsealed";
+/// The header of stores that kept answers in plain text, which are still read.
+const PLAIN_HEADER: &str = "year\tday\tpart\tanswer";
+
+fn parse_key(year: &str, day: &str, part: &str) -> Result<(Puzzle, Stage)> {
+    Ok((Puzzle::new(year.parse()?, day.parse()?)?, part.parse()?))
+}
+
+/// Known answers, keyed by puzzle and part, in a tab-separated file.
+///
+/// The file holds a salted SHA-256 digest of each answer rather than the
+/// answer, so it can be checked in without spoiling anything at a glance;
+/// short numeric answers can still be found by brute force. With a
+/// [`Secret`], each answer is also sealed so that [`Answers::reveal`] can
+/// show it again.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    entries: BTreeMap<(Puzzle, Stage), Entry>,
+    secret: Option<Secret>,
+    plain: bool,
+}
+
+impl Answers {
+    #[must_use]
+    pub fn new(secret: Option<Secret>) -> Self {
+        Self {
+            secret,
+            ..Self::default()
+        }
+    }
+
+    /// Read the store at `path`; empty if there's no file yet. A store with
+    /// answers in plain text is read too, and sealed when saved.
+    pub fn load(path: &Path, secret: Option<Secret>) -> Result<Self> {
+        let mut answers = Self::new(secret);
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(answers),
+            Err(e) => return Err(e.into()),
+        };
+        for (idx, line) in contents.lines().enumerate() {
+            if line.is_empty() || line == HEADER {
+                continue;
+            }
+            if line == PLAIN_HEADER {
+                answers.plain = true;
+                continue;
+            }
+            answers
+                .parse_line(line)
+                .map_err(|e| err!("{}:{}: bad answer line: {}", path.display(), idx + 1, e))?;
+        }
+        Ok(answers)
+    }
+
+    fn parse_line(&mut self, line: &str) -> Result<()> {
+        let fields: Vec<_> = line.split('\t').collect();
+        match fields[..] {
+            [year, day, part, salt, digest, sealed] => {
+                let entry = Entry {
+                    salt: from_hex(salt)?,
+                    digest: from_hex(digest)?,
+                    sealed: (sealed != "-").then(|| from_hex(sealed)).transpose()?,
+                };
+                self.entries.insert(parse_key(year, day, part)?, entry);
+                Ok(())
+            }
+            [year, day, part, answer] if self.plain => {
+                let (puzzle, part) = parse_key(year, day, part)?;
+                self.insert(puzzle, part, answer).map(drop)
+            }
+            _ => Err(err!(
+                "expected 6 tab-separated fields, found {}",
+                fields.len()
+            )),
+        }
+    }
+
+    /// Whether the store was read from a file with answers in plain text.
+    #[must_use]
+    pub fn is_plain(&self) -> bool {
+        self.plain
+    }
+
+    #[must_use]
+    pub fn secret(&self) -> Option<&Secret> {
+        self.secret.as_ref()
+    }
+
+    /// Write the store to `path`, sorted by puzzle and part.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut contents = format!("{HEADER}\n");
+        for ((puzzle, part), entry) in &self.entries {
+            let sealed = entry.sealed.as_deref().map_or("-".to_string(), to_hex);
+            contents += &format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\n",
+                puzzle.year,
+                puzzle.day,
+                part,
+                to_hex(&entry.salt),
+                to_hex(&entry.digest),
+                sealed
+            );
+        }
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn contains(&self, puzzle: Puzzle, part: Stage) -> bool {
+        self.entries.contains_key(&(puzzle, part))
+    }
+
+    /// Store the answer to a part, returning whether it replaced one.
+    pub fn insert(&mut self, puzzle: Puzzle, part: Stage, answer: &str) -> Result<bool> {
+        if part == Stage::Parse {
+            return Err(err!("only parts 1 and 2 have answers"));
+        }
+        if answer.is_empty() || answer.contains(['\t', '\n']) {
+            return Err(err!("cannot store answer {:?}", answer));
+        }
+        let entry = Entry::new(answer, self.secret.as_ref())?;
+        Ok(self.entries.insert((puzzle, part), entry).is_some())
+    }
+
+    #[must_use]
+    pub fn check(&self, puzzle: Puzzle, part: Stage, answer: &str) -> Verdict {
+        match self.entries.get(&(puzzle, part)) {
+            None => Verdict::Unknown,
+            Some(entry) if entry.matches(answer) => Verdict::Correct,
+            Some(_) => Verdict::Wrong {
+                expected: match self.reveal(puzzle, part) {
+                    Some(Revealed::Answer(answer)) => Some(answer),
+                    _ => None,
+                },
+            },
+        }
+    }
+
+    /// The stored answer to a part, which needs the secret it was sealed
+    /// with; `None` if there's no answer or the store has no secret.
+    #[must_use]
+    pub fn reveal(&self, puzzle: Puzzle, part: Stage) -> Option<Revealed> {
+        let entry = self.entries.get(&(puzzle, part))?;
+        Some(entry.reveal(self.secret.as_ref()?))
+    }
+
+    /// The puzzle and part of every stored answer, in order.
+    pub fn keys(&self) -> impl Iterator<Item = (Puzzle, Stage)> + '_ {
+        self.entries.keys().copied()
+    }
+}
+
+/// Panic unless `answer` is the one stored for `part` of `puzzle` in the
+/// workspace that `dir` is in; used by [`aoc_tests!`](crate::aoc_tests!)
+/// so that tests of real inputs don't spell out their answers.
+pub fn assert_stored(dir: &str, puzzle: Puzzle, part: Stage, answer: &str) {
+    let path = Path::new(dir)
+        .ancestors()
+        .map(|dir| dir.join("answers.tsv"))
+        .find(|path| path.exists())
+        .unwrap_or_else(|| panic!("no answers.tsv above {dir}"));
+    let answers = Answers::load(&path, None).unwrap();
+    match answers.check(puzzle, part, answer) {
+        Verdict::Correct => {}
+        Verdict::Wrong { .. } => panic!("{puzzle} part {part}: {answer} is not the stored answer"),
+        Verdict::Unknown => panic!("{puzzle} part {part}: no answer in {}", path.display()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzle() {
+        let puzzle = Puzzle::new(2021, 7).unwrap();
+        assert_eq!(puzzle.member(), "y2021/d07");
+        assert_eq!(puzzle.to_string(), "2021 day 07");
+        assert_eq!(
+            puzzle.input_path(Path::new("/aoc")),
+            PathBuf::from("/aoc/y2021/d07/input.txt")
+        );
+        assert!(Puzzle::new(2014, 1).is_err());
+        assert!(Puzzle::new(2022, 0).is_err());
+        assert!(Puzzle::new(2022, 26).is_err());
+    }
+
+    #[test]
+    fn test_from_member() {
+        assert_eq!(Puzzle::from_member("y2023/d25"), Puzzle::new(2023, 25).ok());
+        for member in ["aoc", "runner", "d05", "y2022/d26", "y2022/dxx", "2022/d05"] {
+            assert_eq!(Puzzle::from_member(member), None, "{member}");
+        }
+    }
+
+    #[test]
+    fn test_puzzles_sort_by_year_then_day() {
+        let mut puzzles = [
+            Puzzle::new(2022, 1).unwrap(),
+            Puzzle::new(2021, 25).unwrap(),
+            Puzzle::new(2021, 3).unwrap(),
+        ];
+        puzzles.sort();
+        assert_eq!(
+            puzzles.iter().map(Puzzle::package).collect::<Vec<_>>(),
+            ["y2021-d03", "y2021-d25", "y2022-d01"]
+        );
+    }
+
+    #[test]
+    fn test_answers_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.tsv");
+        assert_eq!(Answers::load(&path, None).unwrap(), Answers::default());
+
+        let d05 = Puzzle::new(2022, 5).unwrap();
+        let d01 = Puzzle::new(2021, 1).unwrap();
+        let secret = Secret::new("hunter2");
+        let mut answers = Answers::new(Some(secret.clone()));
+        answers.insert(d05, Stage::Part2, "MCD").unwrap();
+        answers.insert(d05, Stage::Part1, "CMZ").unwrap();
+        answers.insert(d01, Stage::Part1, "7").unwrap();
+        answers.save(&path).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("CMZ"), "{contents}");
+        let keys: Vec<_> = contents
+            .lines()
+            .map(|line| line.splitn(4, '\t').take(3).collect::<Vec<_>>().join(" "))
+            .collect();
+        assert_eq!(keys, ["year day part", "2021 1 1", "2022 5 1", "2022 5 2"]);
+        assert_eq!(Answers::load(&path, Some(secret)).unwrap(), answers);
+    }
+
+    #[test]
+    fn test_check() {
+        let puzzle = Puzzle::new(2022, 6).unwrap();
+        let mut answers = Answers::default();
+        assert!(!answers.insert(puzzle, Stage::Part1, "7").unwrap());
+        assert_eq!(answers.check(puzzle, Stage::Part1, "7"), Verdict::Correct);
+        assert_eq!(
+            answers.check(puzzle, Stage::Part1, "8"),
+            Verdict::Wrong { expected: None }
+        );
+        assert_eq!(answers.check(puzzle, Stage::Part2, "19"), Verdict::Unknown);
+        assert!(answers.insert(puzzle, Stage::Part1, "8").unwrap());
+        assert_eq!(answers.check(puzzle, Stage::Part1, "8"), Verdict::Correct);
+    }
+
+    #[test]
+    fn test_salted() {
+        let puzzle = Puzzle::new(2022, 6).unwrap();
+        let mut answers = Answers::default();
+        answers.insert(puzzle, Stage::Part1, "7").unwrap();
+        answers.insert(puzzle, Stage::Part2, "7").unwrap();
+        let [first, second] =
+            [Stage::Part1, Stage::Part2].map(|part| &answers.entries[&(puzzle, part)]);
+        assert_ne!(first.digest, second.digest);
+    }
+
+    #[test]
+    fn test_reveal() {
+        let puzzle = Puzzle::new(2022, 6).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.tsv");
+        let mut answers = Answers::new(Some(Secret::new("hunter2")));
+        answers.insert(puzzle, Stage::Part1, "1234").unwrap();
+        answers.save(&path).unwrap();
+
+        let answers = Answers::load(&path, Some(Secret::new("hunter2\n"))).unwrap();
+        assert_eq!(
+            answers.reveal(puzzle, Stage::Part1),
+            Some(Revealed::Answer("1234".into()))
+        );
+        assert_eq!(
+            answers.check(puzzle, Stage::Part1, "1235"),
+            Verdict::Wrong {
+                expected: Some("1234".into())
+            }
+        );
+        assert_eq!(answers.reveal(puzzle, Stage::Part2), None);
+
+        let answers = Answers::load(&path, Some(Secret::new("letmein"))).unwrap();
+        assert_eq!(
+            answers.reveal(puzzle, Stage::Part1),
+            Some(Revealed::OtherSecret)
+        );
+        let answers = Answers::load(&path, None).unwrap();
+        assert_eq!(answers.reveal(puzzle, Stage::Part1), None);
+        assert_eq!(
+            answers.check(puzzle, Stage::Part1, "1234"),
+            Verdict::Correct
+        );
+    }
+
+    #[test]
+    fn test_unsealed() {
+        let puzzle = Puzzle::new(2022, 6).unwrap();
+        let mut answers = Answers::default();
+        answers.insert(puzzle, Stage::Part1, "1234").unwrap();
+        answers.secret = Some(Secret::new("hunter2"));
+        assert_eq!(
+            answers.reveal(puzzle, Stage::Part1),
+            Some(Revealed::Unsealed)
+        );
+    }
+
+    #[test]
+    fn test_secret_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".aoc").join("secret");
+        let created = Secret::create(&path).unwrap();
+        if std::env::var(Secret::ENV).is_err() {
+            assert_eq!(Secret::load(&path).unwrap(), Some(created));
+        }
+        assert!(Secret::create(&path).is_err());
+    }
+
+    #[test]
+    fn test_load_plain() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.tsv");
+        fs::write(&path, format!("{PLAIN_HEADER}\n2022\t6\t1\t1234\n")).unwrap();
+        let answers = Answers::load(&path, None).unwrap();
+        assert!(answers.is_plain());
+        let puzzle = Puzzle::new(2022, 6).unwrap();
+        assert_eq!(
+            answers.check(puzzle, Stage::Part1, "1234"),
+            Verdict::Correct
+        );
+
+        answers.save(&path).unwrap();
+        assert!(!fs::read_to_string(&path).unwrap().contains("1234"));
+        let answers = Answers::load(&path, None).unwrap();
+        assert!(!answers.is_plain());
+        assert_eq!(
+            answers.check(puzzle, Stage::Part1, "1234"),
+            Verdict::Correct
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_answers() {
+        let puzzle = Puzzle::new(2022, 6).unwrap();
+        let mut answers = Answers::default();
+        assert!(answers.insert(puzzle, Stage::Parse, "1").is_err());
+        assert!(answers.insert(puzzle, Stage::Part1, "").is_err());
+        assert!(answers.insert(puzzle, Stage::Part1, "a\tb").is_err());
+    }
+
+    #[test]
+    fn test_load_reports_bad_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.tsv");
+        for line in [
+            "2022\t26\t1\t00\t00\t-",
+            "2022\t6\t1\tzz\t00\t-",
+            "2022\t6\t1\t1234",
+        ] {
+            fs::write(&path, format!("{HEADER}\n{line}\n")).unwrap();
+            let err = Answers::load(&path, None).unwrap_err().to_string();
+            assert!(err.contains("answers.tsv:2:"), "{err}");
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Read ASCII diagrams by column: [`transpose`] turns a block of text on its
+//! side, and [`labelled_columns`] finds the columns of a drawing from the
+//! labels written under it. Both treat short lines as if they were padded
+//! with spaces, since editors strip trailing whitespace unevenly.
+//!
+//! ```rust
+//! use aoc::text::{labelled_columns, transpose};
+//!
+//! let drawing = "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n";
+//! let columns = labelled_columns(drawing).unwrap();
+//! let labels: Vec<_> = columns.iter().map(|column| column.label).collect();
+//! assert_eq!(labels, ["1", "2", "3"]);
+//! let stack: Vec<_> = columns[1].bottom_up().collect();
+//! assert_eq!(stack, ["[M]", "[C]", "[D]"]);
+//!
+//! assert_eq!(transpose("#.\n#\n##"), ["###", ". #"]);
+//! ```
+use std::ops::Range;
+
+use crate::{diagnostic::DiagnosticExt, err, Result};
+
+/// The columns of `text`, left to right, each read top to bottom with one
+/// character per line and a space where a line is too short to reach it.
+#[must_use]
+pub fn transpose(text: &str) -> Vec<String> {
+    let lines: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+    let width = lines.iter().map(Vec::len).max().unwrap_or_default();
+    (0..width)
+        .map(|x| {
+            lines
+                .iter()
+                .map(|line| line.get(x).copied().unwrap_or(' '))
+                .collect()
+        })
+        .collect()
+}
+
+/// A column of a drawing, found by its label.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Column<'a> {
+    pub label: &'a str,
+    /// For each line above the labels, top to bottom, the word that sits
+    /// over the label, if any.
+    pub cells: Vec<Option<&'a str>>,
+}
+
+impl<'a> Column<'a> {
+    /// The words in the column from the labels upwards, skipping gaps.
+    pub fn bottom_up(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.cells.iter().rev().flatten().copied()
+    }
+}
+
+/// The words of `line` with the range of characters each covers.
+fn words(line: &str) -> Vec<(Range<usize>, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let chars = line.char_indices().chain([(line.len(), ' ')]);
+    for (column, (idx, c)) in chars.enumerate() {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some((column, idx)),
+            (Some((first, from)), true) => {
+                words.push((first..column, &line[from..idx]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+/// The columns of a drawing whose last non-blank line labels them, like the
+/// stacks of crates in 2022 day 5. Each word above the labels belongs to the
+/// label it overlaps, so labels of any width work as long as every word
+/// overlaps exactly one. Blank lines are skipped, and errors point into
+/// `text`.
+pub fn labelled_columns(text: &str) -> Result<Vec<Column<'_>>> {
+    let mut lines: Vec<_> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    let labels = words(lines.pop().ok_or_else(|| err!("no label line"))?);
+    let mut columns: Vec<_> = labels
+        .iter()
+        .map(|&(_, label)| Column {
+            label,
+            cells: vec![None; lines.len()],
+        })
+        .collect();
+    for (row, line) in lines.into_iter().enumerate() {
+        for (span, word) in words(line) {
+            let mut over = labels
+                .iter()
+                .enumerate()
+                .filter(|(_, (label, _))| span.start < label.end && label.start < span.end)
+                .map(|(idx, _)| idx);
+            let (Some(idx), None) = (over.next(), over.next()) else {
+                return Err(err!("{:?} isn't over exactly one label", word)).at(text, word);
+            };
+            let cell = &mut columns[idx].cells[row];
+            if cell.is_some() {
+                return Err(err!("{:?} shares its column with another word", word)).at(text, word);
+            }
+            *cell = Some(word);
+        }
+    }
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::Diagnostic;
+
+    fn column<'a>(columns: &[Column<'a>], label: &str) -> Vec<&'a str> {
+        columns
+            .iter()
+            .find(|column| column.label == label)
+            .unwrap()
+            .bottom_up()
+            .collect()
+    }
+
+    #[test]
+    fn test_transpose() {
+        assert_eq!(transpose("ab\ncd\n"), ["ac", "bd"]);
+        assert_eq!(transpose("a\n\nbcd"), ["a b", "  c", "  d"]);
+        assert!(transpose("").is_empty());
+    }
+
+    #[test]
+    fn test_words() {
+        assert_eq!(words(" [Z]  é1 "), [(1..4, "[Z]"), (6..8, "é1")]);
+        assert!(words("   ").is_empty());
+    }
+
+    #[test]
+    fn test_ragged_edges() {
+        // Trailing spaces stripped from some lines but not others.
+        let drawing = "\n        [Q]\n[N]        \n[Z]     [P]\n 1   2   3 \n";
+        let columns = labelled_columns(drawing).unwrap();
+        assert_eq!(columns.len(), 3);
+        assert_eq!(column(&columns, "1"), ["[Z]", "[N]"]);
+        assert!(column(&columns, "2").is_empty());
+        assert_eq!(column(&columns, "3"), ["[P]", "[Q]"]);
+        assert_eq!(columns[2].cells, [Some("[Q]"), None, Some("[P]")]);
+    }
+
+    #[test]
+    fn test_wide_labels() {
+        let drawing = "[A]                                     [B]\n 1   2   3   4   5   6   7   8   9  10  11\n";
+        let columns = labelled_columns(drawing).unwrap();
+        assert_eq!(column(&columns, "1"), ["[A]"]);
+        assert_eq!(column(&columns, "11"), ["[B]"]);
+        assert!(column(&columns, "10").is_empty());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(labelled_columns("\n \n").is_err());
+        for (drawing, at) in [
+            ("[A]   [B]\n 1   2\n", (1, 7)),
+            ("[A][B]\n 1  2\n", (1, 1)),
+            ("a b\n123\n", (1, 3)),
+        ] {
+            let err = labelled_columns(drawing).unwrap_err();
+            let diagnostic = err.downcast_ref::<Diagnostic>().unwrap();
+            assert_eq!((diagnostic.line, diagnostic.column), at, "{drawing:?}");
+        }
+    }
+}
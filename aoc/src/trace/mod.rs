@@ -0,0 +1,331 @@
+//! Structured events from inside a solution, to see how it reached an answer
+//! without sprinkling `println!`s.
+//!
+//! [`trace!`](crate::trace!) records an event with a name and `key = value`
+//! fields; `key = ?value` records the value's `Debug` output instead. Events
+//! are only kept when the `trace` feature is enabled and the day binary is
+//! run with `--trace` (as `aoc run --trace` does). Without the feature,
+//! [`enabled`] is always false and the optimizer removes the events
+//! entirely.
+//!
+//! ```rust
+//! use aoc::trace::{self, Value};
+//!
+//! fn priority(item: char) -> u32 {
+//!     let priority = u32::from(item) % 32;
+//!     aoc::trace!("item", item = item, priority = priority, upper = ?item.is_uppercase());
+//!     priority
+//! }
+//!
+//! let (sum, events) = trace::collect(|| priority('a') + priority('B'));
+//! assert_eq!(sum, 3);
+//! if trace::enabled() {
+//!     assert_eq!(events[1].fields[1], ("priority", Value::Int(2)));
+//! } else {
+//!     assert!(events.is_empty());
+//! }
+//! ```
+use std::{
+    cell::RefCell,
+    fmt::{self, Debug, Display, Write},
+};
+
+use crate::Result;
+
+/// The value of one field of an [`Event`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    Int(i128),
+    Bool(bool),
+    Str(String),
+}
+
+impl Value {
+    /// A field recorded as its `Debug` output, for `key = ?value`.
+    pub fn debug(value: &impl Debug) -> Self {
+        Value::Str(format!("{value:?}"))
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(value: $ty) -> Self {
+                    Value::Int(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Value {
+    fn from(value: usize) -> Self {
+        Value::Int(value as i128)
+    }
+}
+
+impl From<isize> for Value {
+    fn from(value: isize) -> Self {
+        Value::Int(value as i128)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<char> for Value {
+    fn from(value: char) -> Self {
+        Value::Str(value.to_string())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Str(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Str(value)
+    }
+}
+
+impl From<&String> for Value {
+    fn from(value: &String) -> Self {
+        Value::Str(value.clone())
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{n}"),
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Str(s) => write!(f, "{s:?}"),
+        }
+    }
+}
+
+fn write_json_str(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// One step of a solution, as recorded by [`trace!`](crate::trace!).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    pub name: &'static str,
+    pub fields: Vec<(&'static str, Value)>,
+}
+
+impl Event {
+    /// The event as a single-line JSON object:
+    /// `{"event":"item","fields":{"item":"a","priority":1}}`.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\"event\":");
+        write_json_str(&mut out, self.name);
+        out.push_str(",\"fields\":{");
+        for (idx, (key, value)) in self.fields.iter().enumerate() {
+            if idx > 0 {
+                out.push(',');
+            }
+            write_json_str(&mut out, key);
+            out.push(':');
+            match value {
+                Value::Int(n) => out.push_str(&n.to_string()),
+                Value::Bool(b) => out.push_str(&b.to_string()),
+                Value::Str(s) => write_json_str(&mut out, s),
+            }
+        }
+        out.push_str("}}");
+        out
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)?;
+        for (key, value) in &self.fields {
+            write!(f, " {key}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "trace")]
+static ENABLED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+thread_local! {
+    static EVENTS: RefCell<Vec<Event>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Whether events are being kept.
+#[inline]
+#[must_use]
+pub fn enabled() -> bool {
+    #[cfg(feature = "trace")]
+    return ENABLED.load(std::sync::atomic::Ordering::Relaxed);
+    #[cfg(not(feature = "trace"))]
+    false
+}
+
+/// Start keeping events; fails if `aoc` was built without the `trace`
+/// feature.
+pub fn enable() -> Result<()> {
+    #[cfg(feature = "trace")]
+    {
+        ENABLED.store(true, std::sync::atomic::Ordering::Relaxed);
+        Ok(())
+    }
+    #[cfg(not(feature = "trace"))]
+    Err(crate::err!(
+        "tracing needs the `trace` feature, e.g. `cargo run --features aoc/trace`"
+    ))
+}
+
+/// Record an event; used by [`trace!`](crate::trace!).
+#[doc(hidden)]
+pub fn emit(name: &'static str, fields: Vec<(&'static str, Value)>) {
+    EVENTS.with(|events| events.borrow_mut().push(Event { name, fields }));
+}
+
+/// Run `f`, returning the events it recorded on this thread.
+pub fn collect<T>(f: impl FnOnce() -> T) -> (T, Vec<Event>) {
+    if !enabled() {
+        return (f(), Vec::new());
+    }
+    let outer = EVENTS.with(RefCell::take);
+    let value = f();
+    let events = EVENTS.with(|events| events.replace(outer));
+    (value, events)
+}
+
+/// Record a [`trace`](mod@crate::trace) event: a name followed by `key = value`
+/// fields, where each value converts [`Into`] a [`Value`], or `key = ?value`
+/// to record its `Debug` output.
+#[macro_export]
+macro_rules! trace {
+    ($name:literal $(, $($field:tt)*)?) => {
+        if $crate::trace::enabled() {
+            $crate::trace::emit($name, $crate::__trace_fields!([] $($($field)*)?));
+        }
+    };
+}
+
+/// Turn `trace!` fields into a `Vec` of `(key, Value)`, one at a time.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_fields {
+    ([$($done:expr),*]) => {
+        ::std::vec![$($done),*]
+    };
+    ([$($done:expr),*] $key:ident = ?$value:expr $(, $($rest:tt)*)?) => {
+        $crate::__trace_fields!(
+            [$($done,)* (::std::stringify!($key), $crate::trace::Value::debug(&$value))]
+            $($($rest)*)?
+        )
+    };
+    ([$($done:expr),*] $key:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__trace_fields!(
+            [$($done,)* (::std::stringify!($key), $crate::trace::Value::from($value))]
+            $($($rest)*)?
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_json() {
+        let event = Event {
+            name: "game",
+            fields: vec![
+                ("round", Value::Int(-3)),
+                ("won", Value::Bool(true)),
+                ("note", Value::Str("a \"b\"\n\u{1}".into())),
+            ],
+        };
+        assert_eq!(
+            event.to_json(),
+            r#"{"event":"game","fields":{"round":-3,"won":true,"note":"a \"b\"\n\u0001"}}"#
+        );
+        assert_eq!(
+            event.to_string(),
+            r#"game round=-3 won=true note="a \"b\"\n\u{1}""#
+        );
+    }
+
+    #[test]
+    fn test_macro_fields() {
+        let size = 7_usize;
+        let fields: Vec<(&str, Value)> =
+            __trace_fields!([] size = size, name = "a", kind = ?Some('x'), big = u64::MAX);
+        assert_eq!(
+            fields,
+            [
+                ("size", Value::Int(7)),
+                ("name", Value::Str("a".into())),
+                ("kind", Value::Str("Some('x')".into())),
+                ("big", Value::Int(u64::MAX.into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect() {
+        let (value, events) = collect(|| {
+            trace!("step", n = 1);
+            2
+        });
+        assert_eq!(value, 2);
+        if enabled() {
+            assert_eq!(events.len(), 1);
+        } else {
+            assert!(events.is_empty());
+        }
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn test_collect_enabled() {
+        enable().unwrap();
+        let (_, events) = collect(|| {
+            trace!("outer", n = 1);
+            let (_, inner) = collect(|| trace!("inner"));
+            assert_eq!(inner.len(), 1);
+            trace!("outer", n = 2);
+        });
+        let names: Vec<_> = events.iter().map(|e| e.to_string()).collect();
+        assert_eq!(names, ["outer n=1", "outer n=2"]);
+    }
+
+    #[cfg(not(feature = "trace"))]
+    #[test]
+    fn test_enable_needs_feature() {
+        assert!(enable().is_err());
+        assert!(!enabled());
+    }
+}
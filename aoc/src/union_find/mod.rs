@@ -0,0 +1,317 @@
+//! Disjoint sets, and the connected regions of a [`Grid`] found with them.
+//!
+//! ```rust
+//! use aoc::{grid::{Grid, Point}, union_find::components};
+//!
+//! let map = Grid::parse("..#\n###\n#..", Ok).unwrap();
+//! let air = components(&map, |&c| c == '.');
+//! assert_eq!(air.len(), 2);
+//! let pocket = air.at(Point::new(1, 2)).unwrap();
+//! assert_eq!((pocket.size, pocket.perimeter), (2, 6));
+//! ```
+use crate::grid::{Bounds, Grid, Point};
+
+/// A partition of `0..len` into disjoint sets, merged with
+/// [`union`](Self::union), using union by rank and path compression.
+#[derive(Clone, Debug, Default)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// `len` elements, each in a set of its own.
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+            size: vec![1; len],
+            sets: len,
+        }
+    }
+
+    /// Add an element in a set of its own, returning it.
+    pub fn push(&mut self) -> usize {
+        let element = self.parent.len();
+        self.parent.push(element);
+        self.rank.push(0);
+        self.size.push(1);
+        self.sets += 1;
+        element
+    }
+
+    /// How many elements there are.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// How many sets there are.
+    #[must_use]
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+
+    /// The representative of the set holding `element`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `element` is out of range.
+    pub fn find(&mut self, mut element: usize) -> usize {
+        while self.parent[element] != element {
+            // Path halving: point every other node at its grandparent.
+            let grandparent = self.parent[self.parent[element]];
+            self.parent[element] = grandparent;
+            element = grandparent;
+        }
+        element
+    }
+
+    /// Merge the sets holding `a` and `b`; `false` if they were already one.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (root, child) = if self.rank[a] < self.rank[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        self.parent[child] = root;
+        self.size[root] += self.size[child];
+        if self.rank[a] == self.rank[b] {
+            self.rank[root] += 1;
+        }
+        self.sets -= 1;
+        true
+    }
+
+    /// Whether `a` and `b` are in the same set.
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// How many elements are in the set holding `element`.
+    pub fn size_of(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.size[root]
+    }
+}
+
+/// A connected region of a grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Component {
+    /// How many cells it covers.
+    pub size: usize,
+    pub bounds: Bounds,
+    /// How many cell edges separate it from cells outside it, counting the
+    /// edge of the grid.
+    pub perimeter: usize,
+    /// Whether it reaches the edge of the grid, as air open to the outside
+    /// does.
+    pub touches_edge: bool,
+}
+
+/// The regions of a grid found by [`components`].
+#[derive(Clone, Debug)]
+pub struct Components {
+    labels: Grid<Option<usize>>,
+    components: Vec<Component>,
+}
+
+impl Components {
+    /// How many regions there are.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// The label of the region covering `point`, numbered from 0 in the
+    /// order their first cells come row by row.
+    #[must_use]
+    pub fn label(&self, point: Point) -> Option<usize> {
+        self.labels.get(point).copied().flatten()
+    }
+
+    /// The region covering `point`.
+    #[must_use]
+    pub fn at(&self, point: Point) -> Option<&Component> {
+        self.label(point).map(|label| &self.components[label])
+    }
+
+    /// The region labelled `label`.
+    #[must_use]
+    pub fn get(&self, label: usize) -> Option<&Component> {
+        self.components.get(label)
+    }
+
+    /// Every region, by label.
+    pub fn iter(&self) -> impl Iterator<Item = &Component> {
+        self.components.iter()
+    }
+}
+
+/// The orthogonally connected regions of the cells of `grid` for which
+/// `include` is true.
+pub fn components<T>(grid: &Grid<T>, include: impl Fn(&T) -> bool) -> Components {
+    let included = |point| grid.get(point).is_some_and(&include);
+    let index = |point: Point| {
+        // Points from the grid itself are in range.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        {
+            point.y as usize * grid.width() + point.x as usize
+        }
+    };
+    let mut sets = UnionFind::new(grid.width() * grid.height());
+    for (point, cell) in grid.iter() {
+        if !include(cell) {
+            continue;
+        }
+        for next in [point + Point::RIGHT, point + Point::DOWN] {
+            if included(next) {
+                sets.union(index(point), index(next));
+            }
+        }
+    }
+
+    let mut labels = Grid::new(grid.width(), grid.height(), None);
+    let mut by_root = vec![None; sets.len()];
+    let mut components: Vec<Component> = Vec::new();
+    for (point, cell) in grid.iter() {
+        if !include(cell) {
+            continue;
+        }
+        let root = sets.find(index(point));
+        let label = *by_root[root].get_or_insert_with(|| {
+            components.push(Component {
+                size: 0,
+                bounds: Bounds::at(point),
+                perimeter: 0,
+                touches_edge: false,
+            });
+            components.len() - 1
+        });
+        labels[point] = Some(label);
+        let component = &mut components[label];
+        component.size += 1;
+        component.bounds.extend(point);
+        for next in point.orthogonal() {
+            if !included(next) {
+                component.perimeter += 1;
+                component.touches_edge |= !grid.contains(next);
+            }
+        }
+    }
+    Components { labels, components }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Grid<char> {
+        Grid::parse(text, Ok).unwrap()
+    }
+
+    #[test]
+    fn test_union_find() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!((sets.len(), sets.sets()), (6, 6));
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+        assert!(sets.connected(0, 3));
+        assert!(!sets.connected(0, 4));
+        assert_eq!(sets.size_of(2), 4);
+        assert_eq!(sets.size_of(5), 1);
+        assert_eq!(sets.sets(), 3);
+
+        let element = sets.push();
+        assert_eq!(element, 6);
+        sets.union(element, 5);
+        assert_eq!((sets.len(), sets.sets(), sets.size_of(5)), (7, 3, 2));
+        assert!(UnionFind::default().is_empty());
+    }
+
+    #[test]
+    fn test_long_chain() {
+        let mut sets = UnionFind::new(10_000);
+        for element in 1..sets.len() {
+            sets.union(element - 1, element);
+        }
+        assert_eq!(sets.sets(), 1);
+        assert_eq!(sets.size_of(0), 10_000);
+        assert!(sets.connected(0, 9_999));
+    }
+
+    #[test]
+    fn test_components() {
+        let map = grid(
+            "\
+##..#
+#..##
+##.#.
+.###.",
+        );
+        let walls = components(&map, |&c| c == '#');
+        assert_eq!(walls.len(), 1);
+        let main = walls.at(Point::ORIGIN).unwrap();
+        assert_eq!(main.size, 12);
+        assert_eq!(
+            (main.bounds.min, main.bounds.max),
+            (Point::new(0, 0), Point::new(4, 3))
+        );
+        assert_eq!(walls.label(Point::new(3, 3)), Some(0));
+        assert_eq!(walls.label(Point::new(2, 0)), None);
+
+        let air = components(&map, |&c| c == '.');
+        let labels: Vec<_> = [(2, 0), (4, 2), (0, 3)]
+            .map(|(x, y)| air.label(Point::new(x, y)).unwrap())
+            .to_vec();
+        assert_eq!(labels, [0, 1, 2]);
+        assert_eq!(air.get(0).map(|c| c.size), Some(5));
+        assert_eq!(air.iter().map(|c| c.size).sum::<usize>(), 8);
+    }
+
+    #[test]
+    fn test_perimeter() {
+        // A ring of walls around a pocket of air, with open air outside.
+        let map = grid(
+            "\
+.....
+.###.
+.#.#.
+.###.
+.....",
+        );
+        let air = components(&map, |&c| c == '.');
+        assert_eq!(air.len(), 2);
+        let outside = air.at(Point::ORIGIN).unwrap();
+        let pocket = air.at(Point::new(2, 2)).unwrap();
+        assert!(outside.touches_edge);
+        assert!(!pocket.touches_edge);
+        assert_eq!((pocket.size, pocket.perimeter), (1, 4));
+        // 20 edges of the grid and 12 faces of the ring.
+        assert_eq!((outside.size, outside.perimeter), (16, 32));
+
+        let ring = components(&map, |&c| c == '#');
+        assert_eq!(ring.get(0).unwrap().perimeter, 16);
+        assert!(components(&map, |_| false).is_empty());
+        assert_eq!(components(&map, |_| true).get(0).unwrap().perimeter, 20);
+    }
+}
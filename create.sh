@@ -8,18 +8,24 @@ shopt -s inherit_errexit
 set -x
 
 main() {
-  local day=$1
+  local year=$1
+  local day=$2
   local zero_padded_day=$(printf '%02d' "${day}")
-  local dir=$(printf 'd%s' "${zero_padded_day}")
+  local dir="y${year}/d${zero_padded_day}"
+  local crate="y${year}_d${zero_padded_day}"
+  mkdir -p "y${year}"
   cp -a ./template/. "${dir}"
 
-  sed -i "/^${dir}$/d" ./.gitignore
-  sed -i "s/# \(\"${dir}\",\)/\1/" ./Cargo.toml
-  sed -i 's/\(println!("day \)\(part \)/\1'"${zero_padded_day}"' \2/' ./"${dir}"/src/main.rs
-  sed -i 's/name = "d/&'"${zero_padded_day}"'/' ./"${dir}"/Cargo.toml
+  sed -i "s|^]$|    \"${dir}\",\n]|" ./Cargo.toml
+  sed -i "s/Day00/Day${zero_padded_day}/g; s/y0000_d00::/${crate}::/g; s/const YEAR: u16 = 0;/const YEAR: u16 = ${year};/; s/const DAY: u8 = 0;/const DAY: u8 = ${day};/" ./"${dir}"/src/{lib,main}.rs
+  sed -i "s/name = \"y0000-d00\"/name = \"y${year}-d${zero_padded_day}\"/" ./"${dir}"/Cargo.toml
 
   pushd "${dir}"
-  aoc download --input-file input.txt -d "${day}"
+  aoc download --input-file input.txt -y "${year}" -d "${day}"
+  # Keep answers out of the checked-in puzzle text; they're in answers.tsv.
+  if [[ -f puzzle.md ]]; then
+    sed -i '/^Your puzzle answer was /,+1d' puzzle.md
+  fi
 }
 
 main "$@"
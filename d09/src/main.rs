@@ -1,37 +0,0 @@
-// #![warn(clippy::pedantic)]
-use aoc::{err, localpath, parse_input, Error, Result};
-
-const INPUT: &str = include_str!("../input.txt");
-
-fn part1() -> Result<u32> {
-    todo!()
-}
-
-fn part2() -> Result<u32> {
-    todo!()
-}
-
-fn main() -> Result<()> {
-    // let input = parse_input!(localpath!("input.txt"))?;
-    // println!("day 09 part 1: {}", part1(&input)?);
-    // println!("day 09 part 2: {}", part2(&input)?);
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    static EXAMPLE_INPUT: &str = r#"
-"#;
-
-    #[test]
-    fn test_part1() {
-        assert!(false);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert!(false);
-    }
-}
@@ -0,0 +1,15 @@
+# 2022 day 1: the calories carried by the top one and top three elves.
+function add(sum) {
+  if (sum > first) { third = second; second = first; first = sum }
+  else if (sum > second) { third = second; second = sum }
+  else if (sum > third) { third = sum }
+}
+
+/^$/ { add(sum); sum = 0; next }
+{ sum += $1 }
+
+END {
+  add(sum)
+  print "answer 1 " first
+  print "answer 2 " first + second + third
+}
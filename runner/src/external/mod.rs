@@ -0,0 +1,227 @@
+//! Solvers written in other languages, run under the same limits and checked
+//! against the same answers as the Rust days.
+//!
+//! Each `[[solver]]` in `solvers.toml` names a command to run for some days
+//! of a year, from the workspace root or `dir`, where `{year}` and `{day}`
+//! (zero-padded) are replaced in both:
+//!
+//! ```toml
+//! [[solver]]
+//! name = "awk"
+//! year = 2022
+//! days = [1]
+//! command = ["awk", "-f", "external/awk/y{year}/d{day}.awk"]
+//! ```
+//!
+//! The command gets the puzzle input on stdin and prints the same lines as a
+//! day binary run with `--protocol`: `answer 1 <answer>` and `answer 2
+//! <answer>`, plus `time <stage> <nanoseconds>` if it times itself. A part
+//! without a `time` line is given how long the whole command took.
+use std::{
+    fs, io,
+    path::Path,
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use aoc::{
+    err,
+    solution::{Record, Report, Stage},
+    store::Puzzle,
+    Result,
+};
+use serde::Deserialize;
+
+use crate::sandbox::{self, Limits};
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    #[serde(default, rename = "solver")]
+    solvers: Vec<Solver>,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Solver {
+    pub name: String,
+    pub year: u16,
+    pub days: Vec<u8>,
+    pub command: Vec<String>,
+    /// Where to run the command, relative to the workspace root.
+    pub dir: Option<String>,
+}
+
+/// The solvers configured in `path`, or none if it doesn't exist.
+pub fn load(path: &Path) -> Result<Vec<Solver>> {
+    let config = match fs::read_to_string(path) {
+        Ok(config) => config,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    parse(&config).map_err(|e| err!("{}: {}", path.display(), e))
+}
+
+fn parse(config: &str) -> Result<Vec<Solver>> {
+    let config: Config = toml::from_str(config)?;
+    for solver in &config.solvers {
+        if solver.name.is_empty() || solver.name.contains(char::is_whitespace) {
+            return Err(err!("solver name {:?} must be one word", solver.name));
+        }
+        if solver.command.is_empty() {
+            return Err(err!("solver {} has no command", solver.name));
+        }
+        for &day in &solver.days {
+            Puzzle::new(solver.year, day)?;
+        }
+    }
+    Ok(config.solvers)
+}
+
+fn fill(template: &str, puzzle: Puzzle) -> String {
+    template
+        .replace("{year}", &puzzle.year.to_string())
+        .replace("{day}", &format!("{:02}", puzzle.day))
+}
+
+impl Solver {
+    #[must_use]
+    pub fn solves(&self, puzzle: Puzzle) -> bool {
+        self.year == puzzle.year && self.days.contains(&puzzle.day)
+    }
+
+    fn command(&self, root: &Path, puzzle: Puzzle) -> Command {
+        let mut args = self.command.iter().map(|arg| fill(arg, puzzle));
+        let mut command = Command::new(args.next().unwrap_or_default());
+        command.args(args).current_dir(
+            self.dir
+                .as_deref()
+                .map_or_else(|| root.to_path_buf(), |dir| root.join(fill(dir, puzzle))),
+        );
+        command
+    }
+
+    /// Solve `puzzle` with its input from the workspace at `root`, returning
+    /// a report for each part.
+    pub fn run(&self, root: &Path, puzzle: Puzzle, limits: Limits) -> Result<Vec<Report>> {
+        let input = fs::read_to_string(puzzle.input_path(root))?;
+        let start = Instant::now();
+        let output = sandbox::run(self.command(root, puzzle), Some(input), limits)?;
+        reports(&output, start.elapsed())
+    }
+}
+
+/// The report for each part from a solver's `output`, timed by `wall` unless
+/// it reported a time itself.
+fn reports(output: &str, wall: Duration) -> Result<Vec<Report>> {
+    let records = output
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<Record>>>()?;
+    [Stage::Part1, Stage::Part2]
+        .into_iter()
+        .map(|stage| {
+            let answer = records.iter().find_map(|record| match record {
+                Record::Answer { stage: s, answer } if *s == stage => Some(answer.clone()),
+                _ => None,
+            });
+            let nanos = records.iter().find_map(|record| match record {
+                Record::Time { stage: s, nanos } if *s == stage => Some(*nanos),
+                _ => None,
+            });
+            Ok(Report {
+                stage,
+                answer: Some(answer.ok_or_else(|| err!("no answer for part {}", stage))?),
+                elapsed: match nanos {
+                    Some(nanos) => Duration::from_nanos(u64::try_from(nanos)?),
+                    None => wall,
+                },
+                trace: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[[solver]]
+name = "awk"
+year = 2022
+days = [1, 2]
+command = ["awk", "-f", "external/awk/y{year}/d{day}.awk"]
+
+[[solver]]
+name = "sh"
+year = 2022
+days = [3]
+command = ["sh", "-c", "wc -l | sed 's/^ */answer 1 /'; echo answer 2 x"]
+dir = "y{year}/d{day}"
+"#;
+
+    #[test]
+    fn test_parse() {
+        let solvers = parse(CONFIG).unwrap();
+        assert_eq!(solvers.len(), 2);
+        assert_eq!(solvers[0].days, [1, 2]);
+        assert_eq!(solvers[1].dir.as_deref(), Some("y{year}/d{day}"));
+        assert!(parse("").unwrap().is_empty());
+        for bad in [
+            "[[solver]]\nname = \"a b\"\nyear = 2022\ndays = [1]\ncommand = [\"x\"]",
+            "[[solver]]\nname = \"a\"\nyear = 2022\ndays = [1]\ncommand = []",
+            "[[solver]]\nname = \"a\"\nyear = 2022\ndays = [26]\ncommand = [\"x\"]",
+            "[[solver]]\nname = \"a\"\nyear = 2022\ndays = [1]\ncommand = [\"x\"]\nargs = []",
+        ] {
+            assert!(parse(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_command() {
+        let solvers = parse(CONFIG).unwrap();
+        let puzzle = Puzzle::new(2022, 1).unwrap();
+        assert!(solvers[0].solves(puzzle));
+        assert!(!solvers[1].solves(puzzle));
+        let command = solvers[0].command(Path::new("/aoc"), puzzle);
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, ["-f", "external/awk/y2022/d01.awk"]);
+        assert_eq!(command.get_current_dir(), Some(Path::new("/aoc")));
+
+        let puzzle = Puzzle::new(2022, 3).unwrap();
+        let command = solvers[1].command(Path::new("/aoc"), puzzle);
+        assert_eq!(command.get_current_dir(), Some(Path::new("/aoc/y2022/d03")));
+    }
+
+    #[test]
+    fn test_reports() {
+        let wall = Duration::from_millis(3);
+        let parts = reports("answer 1 24000\ntime 1 1500\n\nanswer 2 45000\n", wall).unwrap();
+        assert_eq!(parts[0].answer.as_deref(), Some("24000"));
+        assert_eq!(parts[0].elapsed, Duration::from_nanos(1500));
+        assert_eq!(parts[1].answer.as_deref(), Some("45000"));
+        assert_eq!(parts[1].elapsed, wall);
+        assert!(reports("answer 1 24000\n", wall).is_err());
+        assert!(reports("24000\n45000\n", wall).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("y2022").join("d03");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("input.txt"), "a\nb\nc\n").unwrap();
+        let solvers = parse(CONFIG).unwrap();
+        let limits = Limits {
+            timeout: Duration::from_secs(10),
+            memory: None,
+        };
+        let reports = solvers[1]
+            .run(root.path(), Puzzle::new(2022, 3).unwrap(), limits)
+            .unwrap();
+        let answers: Vec<_> = reports.iter().map(|r| r.answer.as_deref()).collect();
+        assert_eq!(answers, [Some("3"), Some("x")]);
+    }
+}
@@ -0,0 +1,176 @@
+//! The timing history: a tab-separated file with one line per timed stage of
+//! each run, appended to by `aoc run` and read by `aoc perf`.
+use std::{
+    fmt::{self, Display},
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    result,
+    str::FromStr,
+};
+
+use aoc::{err, solution::Stage, store::Puzzle, Error, Result};
+
+const HEADER: &str = "timestamp\tcommit\tmachine\tyear\tday\tstage\tnanos";
+
+/// One timed stage of one run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// `git describe` of the workspace, with `-dirty` for uncommitted changes.
+    pub commit: String,
+    /// See [`crate::machine::fingerprint`].
+    pub machine: String,
+    pub puzzle: Puzzle,
+    pub stage: Stage,
+    pub nanos: u128,
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.timestamp,
+            self.commit,
+            self.machine,
+            self.puzzle.year,
+            self.puzzle.day,
+            self.stage,
+            self.nanos
+        )
+    }
+}
+
+impl FromStr for Entry {
+    type Err = Error;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let fields: Vec<_> = s.split('\t').collect();
+        let [timestamp, commit, machine, year, day, stage, nanos] = fields[..] else {
+            return Err(err!(
+                "expected 7 tab-separated fields, found {}",
+                fields.len()
+            ));
+        };
+        Ok(Self {
+            timestamp: timestamp.parse()?,
+            commit: commit.to_string(),
+            machine: machine.to_string(),
+            puzzle: Puzzle::new(year.parse()?, day.parse()?)?,
+            stage: stage.parse()?,
+            nanos: nanos.parse()?,
+        })
+    }
+}
+
+pub struct History {
+    path: PathBuf,
+}
+
+impl History {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append `entries`, creating the file (and its directory) if needed.
+    pub fn append(&self, entries: &[Entry]) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        if file.metadata()?.len() == 0 {
+            writeln!(file, "{HEADER}")?;
+        }
+        for entry in entries {
+            writeln!(file, "{entry}")?;
+        }
+        Ok(())
+    }
+
+    /// Every entry in the order it was recorded; empty if there's no history
+    /// yet.
+    pub fn load(&self) -> Result<Vec<Entry>> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty() && *line != HEADER)
+            .map(|(idx, line)| {
+                line.parse().map_err(|e| {
+                    err!(
+                        "{}:{}: bad history line: {}",
+                        self.path.display(),
+                        idx + 1,
+                        e
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: u8, stage: Stage, nanos: u128) -> Entry {
+        Entry {
+            timestamp: 1_670_000_000,
+            commit: "0a4fb85-dirty".into(),
+            machine: "0123456789abcdef".into(),
+            puzzle: Puzzle::new(2022, day).unwrap(),
+            stage,
+            nanos,
+        }
+    }
+
+    #[test]
+    fn test_entry_roundtrip() {
+        let entry = entry(5, Stage::Part1, 101_618);
+        let line = entry.to_string();
+        assert_eq!(
+            line,
+            "1670000000\t0a4fb85-dirty\t0123456789abcdef\t2022\t5\t1\t101618"
+        );
+        assert_eq!(line.parse::<Entry>().unwrap(), entry);
+        assert!("1670000000\t0a4fb85\t2022\t5\t1".parse::<Entry>().is_err());
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = History::new(dir.path().join(".aoc/history.tsv"));
+        assert_eq!(history.load().unwrap(), Vec::new());
+
+        let first = [entry(1, Stage::Parse, 10), entry(1, Stage::Part1, 20)];
+        let second = [entry(2, Stage::Part2, 30)];
+        history.append(&first).unwrap();
+        history.append(&second).unwrap();
+
+        let contents = fs::read_to_string(history.path()).unwrap();
+        assert_eq!(contents.lines().filter(|&l| l == HEADER).count(), 1);
+        assert_eq!(history.load().unwrap(), [&first[..], &second[..]].concat());
+    }
+
+    #[test]
+    fn test_load_reports_bad_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = History::new(dir.path().join("history.tsv"));
+        fs::write(history.path(), format!("{HEADER}\nnot an entry\n")).unwrap();
+        let err = history.load().unwrap_err().to_string();
+        assert!(err.contains("history.tsv:2:"), "{err}");
+    }
+}
@@ -0,0 +1,515 @@
+//! A private leaderboard, read from the JSON that Advent of Code serves at
+//! `/<year>/leaderboard/private/view/<id>.json`: overall standings with the
+//! local score recalculated from each member's star times, and how long each
+//! member took over one day's parts.
+//!
+//! The local score gives each star `n - rank` points, where `n` is the number
+//! of members and `rank` counts from 0 in the order the star was earned.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    fs,
+    path::Path,
+    process::Command,
+    time::{Duration, SystemTime},
+};
+
+use aoc::{err, Result};
+use serde::Deserialize;
+
+/// Advent of Code asks that private leaderboards be fetched at most once
+/// every 15 minutes.
+const REFRESH: Duration = Duration::from_mins(15);
+
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: BTreeMap<u64, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    /// Day, then part, to when that star was earned.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    /// Seconds since the Unix epoch.
+    pub get_star_ts: i64,
+    /// Orders stars earned in the same second.
+    #[serde(default)]
+    pub star_index: u64,
+}
+
+impl Member {
+    /// The name Advent of Code shows, for members without a public name too.
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    fn star(&self, day: u8, part: u8) -> Option<&Star> {
+        self.completion_day_level.get(&day)?.get(&part)
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn year(&self) -> Result<u16> {
+        self.event
+            .parse()
+            .map_err(|_| err!("leaderboard event is not a year: {:?}", self.event))
+    }
+
+    /// Each member's local score, leaving out the `excluded` days.
+    pub fn scores(&self, excluded: &[u8]) -> BTreeMap<u64, u32> {
+        let mut scores: BTreeMap<u64, u32> = self.members.keys().map(|&id| (id, 0)).collect();
+        let days: BTreeSet<u8> = self
+            .members
+            .values()
+            .flat_map(|member| member.completion_day_level.keys().copied())
+            .filter(|day| !excluded.contains(day))
+            .collect();
+        for day in days {
+            for (id, points) in self.points(day) {
+                *scores.entry(id).or_default() += points;
+            }
+        }
+        scores
+    }
+
+    /// The points each member earned on `day`, from both parts.
+    fn points(&self, day: u8) -> BTreeMap<u64, u32> {
+        let n = u32::try_from(self.members.len()).unwrap_or(u32::MAX);
+        let mut points = BTreeMap::new();
+        for part in [1, 2] {
+            let mut earned: Vec<_> = self
+                .members
+                .values()
+                .filter_map(|member| {
+                    let star = member.star(day, part)?;
+                    Some((star.get_star_ts, star.star_index, member.id))
+                })
+                .collect();
+            earned.sort_unstable();
+            for (rank, (_, _, id)) in (0..).zip(earned) {
+                *points.entry(id).or_default() += n.saturating_sub(rank);
+            }
+        }
+        points
+    }
+
+    /// Every member, best first: by score, then stars, then who got their
+    /// last star earliest.
+    pub fn standings(&self, excluded: &[u8]) -> Vec<Standing<'_>> {
+        let scores = self.scores(excluded);
+        let mut standings: Vec<_> = self
+            .members
+            .values()
+            .map(|member| Standing {
+                member,
+                score: scores[&member.id],
+            })
+            .collect();
+        standings.sort_by_key(|s| {
+            (
+                std::cmp::Reverse(s.score),
+                std::cmp::Reverse(s.member.stars),
+                s.last_star(),
+                s.member.id,
+            )
+        });
+        standings
+    }
+
+    /// How each member who got a star on `day` did, best first.
+    pub fn day(&self, day: u8) -> Result<Vec<DayResult<'_>>> {
+        let unlock = unlock(self.year()?, day);
+        let points = self.points(day);
+        let mut results: Vec<_> = self
+            .members
+            .values()
+            .filter_map(|member| {
+                let part1 = member.star(day, 1)?.get_star_ts - unlock;
+                let part2 = member.star(day, 2).map(|star| star.get_star_ts - unlock);
+                Some(DayResult {
+                    member,
+                    part1,
+                    part2,
+                    points: points.get(&member.id).copied().unwrap_or_default(),
+                })
+            })
+            .collect();
+        results.sort_by_key(|r| {
+            (
+                std::cmp::Reverse(r.points),
+                r.part2.unwrap_or(i64::MAX),
+                r.part1,
+                r.member.id,
+            )
+        });
+        Ok(results)
+    }
+}
+
+pub struct Standing<'a> {
+    pub member: &'a Member,
+    pub score: u32,
+}
+
+impl Standing<'_> {
+    fn last_star(&self) -> i64 {
+        self.member
+            .completion_day_level
+            .values()
+            .flat_map(BTreeMap::values)
+            .map(|star| star.get_star_ts)
+            .max()
+            .unwrap_or(i64::MAX)
+    }
+
+    /// One character per day up to `days`: `*` for both stars, `+` for only
+    /// the first, `.` for none.
+    fn stars(&self, days: u8) -> String {
+        (1..=days)
+            .map(|day| match self.member.completion_day_level.get(&day) {
+                Some(parts) if parts.len() >= 2 => '*',
+                Some(_) => '+',
+                None => '.',
+            })
+            .collect()
+    }
+}
+
+/// One member's times on one day, in seconds since the puzzle unlocked.
+pub struct DayResult<'a> {
+    pub member: &'a Member,
+    pub part1: i64,
+    pub part2: Option<i64>,
+    pub points: u32,
+}
+
+impl DayResult<'_> {
+    /// How long part 2 took after part 1.
+    pub fn delta(&self) -> Option<i64> {
+        Some(self.part2? - self.part1)
+    }
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// When a puzzle unlocks: midnight US Eastern (UTC-5) on its day of December.
+pub fn unlock(year: u16, day: u8) -> i64 {
+    days_from_civil(year.into(), 12, day.into()) * 86_400 + 5 * 3600
+}
+
+/// A duration like `1:02:03`, or `2d 01:02:03` past a day.
+pub fn format_duration(secs: i64) -> String {
+    let (days, rest) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+    let (hours, minutes, seconds) = (rest / 3600, rest % 3600 / 60, rest % 60);
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    }
+}
+
+/// Columns are left-aligned unless listed in `right`.
+fn table(headers: &[&str], right: &[usize], rows: &[Vec<String>], markdown: bool) -> String {
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .chain([headers[col].len(), if markdown { 3 } else { 0 }])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    let line = |cells: Vec<String>| {
+        let cells = cells
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(col, (cell, &width))| {
+                if right.contains(&col) {
+                    format!("{cell:>width$}")
+                } else {
+                    format!("{cell:<width$}")
+                }
+            });
+        let joined = if markdown {
+            format!("| {} |", cells.collect::<Vec<_>>().join(" | "))
+        } else {
+            cells.collect::<Vec<_>>().join("  ")
+        };
+        joined.trim_end().to_string() + "\n"
+    };
+
+    let mut out = line(headers.iter().map(ToString::to_string).collect());
+    if markdown {
+        let rule = widths.iter().enumerate().map(|(col, &width)| {
+            if right.contains(&col) {
+                format!("{}:", "-".repeat(width - 1))
+            } else {
+                "-".repeat(width)
+            }
+        });
+        out += &line(rule.collect());
+    }
+    for row in rows {
+        out += &line(row.clone());
+    }
+    out
+}
+
+/// The overall standings as a table.
+pub fn render_standings(board: &Leaderboard, excluded: &[u8], markdown: bool) -> String {
+    let standings = board.standings(excluded);
+    let days = board
+        .members
+        .values()
+        .filter_map(|member| member.completion_day_level.keys().max())
+        .max()
+        .copied()
+        .unwrap_or_default();
+    let rows: Vec<_> = standings
+        .iter()
+        .enumerate()
+        .map(|(idx, standing)| {
+            vec![
+                (idx + 1).to_string(),
+                standing.member.display_name(),
+                standing.score.to_string(),
+                standing.member.stars.to_string(),
+                standing.stars(days),
+            ]
+        })
+        .collect();
+    let mut out = table(
+        &["#", "Name", "Score", "Stars", "Days"],
+        &[0, 2, 3],
+        &rows,
+        markdown,
+    );
+    if !excluded.is_empty() {
+        let days: Vec<_> = excluded.iter().map(ToString::to_string).collect();
+        let _ = write!(out, "\nScores leave out day(s) {}.\n", days.join(", "));
+    }
+    out
+}
+
+/// Each member's times on `day` as a table.
+pub fn render_day(board: &Leaderboard, day: u8, markdown: bool) -> Result<String> {
+    let optional = |secs: Option<i64>| secs.map(format_duration).unwrap_or_default();
+    let rows: Vec<_> = board
+        .day(day)?
+        .iter()
+        .enumerate()
+        .map(|(idx, result)| {
+            vec![
+                (idx + 1).to_string(),
+                result.member.display_name(),
+                format_duration(result.part1),
+                optional(result.part2),
+                optional(result.delta()),
+                result.points.to_string(),
+            ]
+        })
+        .collect();
+    Ok(table(
+        &["#", "Name", "Part 1", "Part 2", "Delta", "Points"],
+        &[0, 2, 3, 4, 5],
+        &rows,
+        markdown,
+    ))
+}
+
+/// The leaderboard JSON from `cache` if it's recent enough, otherwise
+/// fetched with `curl` using the session cookie in `AOC_SESSION` or
+/// `~/.adventofcode.session` (as used by `aoc-cli`) and written to `cache`.
+pub fn load_cached(cache: &Path, year: u16, id: u64) -> Result<String> {
+    let age = fs::metadata(cache)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+    if age.is_some_and(|age| age < REFRESH) {
+        return Ok(fs::read_to_string(cache)?);
+    }
+    match fetch(year, id) {
+        Ok(json) => {
+            if let Some(dir) = cache.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(cache, &json)?;
+            Ok(json)
+        }
+        Err(e) if age.is_some() => {
+            eprintln!("using {} after failing to fetch: {e}", cache.display());
+            Ok(fs::read_to_string(cache)?)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// The session cookie from `AOC_SESSION` or `~/.adventofcode.session`.
+pub fn session() -> Result<String> {
+    if let Ok(session) = std::env::var("AOC_SESSION") {
+        return Ok(session);
+    }
+    let home = std::env::var("HOME").map_err(|_| err!("HOME is not set"))?;
+    let path = Path::new(&home).join(".adventofcode.session");
+    fs::read_to_string(&path)
+        .map(|session| session.trim().to_string())
+        .map_err(|e| err!("no AOC_SESSION and can't read {}: {}", path.display(), e))
+}
+
+fn fetch(year: u16, id: u64) -> Result<String> {
+    let url = format!("https://adventofcode.com/{year}/leaderboard/private/view/{id}.json");
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location"])
+        .args(["--user-agent", "aoc22-rust leaderboard viewer"])
+        .args(["--cookie", &format!("session={}", session()?)])
+        .arg(&url)
+        .output()?;
+    if !output.status.success() {
+        return Err(err!(
+            "fetching {} failed: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let json = String::from_utf8(output.stdout)?;
+    // A bad session gets redirected to a login page rather than an error.
+    Leaderboard::parse(&json)?;
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../../fixtures/leaderboard-2022.json");
+
+    fn board() -> Leaderboard {
+        Leaderboard::parse(FIXTURE).unwrap()
+    }
+
+    #[test]
+    fn test_unlock() {
+        // 2022-12-01T05:00:00Z
+        assert_eq!(unlock(2022, 1), 1_669_870_800);
+        assert_eq!(unlock(2022, 2) - unlock(2022, 1), 86_400);
+        // 2020-12-25T05:00:00Z
+        assert_eq!(unlock(2020, 25), 1_608_872_400);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0:00:00");
+        assert_eq!(format_duration(700), "0:11:40");
+        assert_eq!(format_duration(86_400 + 3723), "1d 01:02:03");
+    }
+
+    #[test]
+    fn test_scores_match_reported() {
+        let scores = board().scores(&[]);
+        let json: serde_json::Value = serde_json::from_str(FIXTURE).unwrap();
+        for (id, member) in json["members"].as_object().unwrap() {
+            let id: u64 = id.parse().unwrap();
+            assert_eq!(
+                u64::from(scores[&id]),
+                member["local_score"].as_u64().unwrap(),
+                "{id}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_scores_excluding_days() {
+        let scores = board().scores(&[1]);
+        assert_eq!(
+            scores.into_iter().collect::<Vec<_>>(),
+            [(1001, 8), (1002, 3), (1003, 0), (1004, 0)]
+        );
+    }
+
+    #[test]
+    fn test_standings() {
+        let board = board();
+        let names: Vec<_> = board
+            .standings(&[])
+            .iter()
+            .map(|s| s.member.display_name())
+            .collect();
+        assert_eq!(names, ["Alice", "Bob", "(anonymous user #1003)", "Carol"]);
+    }
+
+    #[test]
+    fn test_day() {
+        let board = board();
+        let results = board.day(1).unwrap();
+        let rows: Vec<_> = results
+            .iter()
+            .map(|r| (r.member.id, r.part1, r.part2, r.delta(), r.points))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                (1001, 300, Some(420), Some(120), 7),
+                (1002, 200, Some(900), Some(700), 7),
+                (1003, 5000, None, None, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_standings() {
+        assert_eq!(
+            render_standings(&board(), &[], false),
+            "\
+#  Name                    Score  Stars  Days
+1  Alice                      15      4  **
+2  Bob                        10      3  *+
+3  (anonymous user #1003)      2      1  +.
+4  Carol                       0      0  ..
+"
+        );
+    }
+
+    #[test]
+    fn test_render_day_markdown() {
+        assert_eq!(
+            render_day(&board(), 2, true).unwrap(),
+            "\
+|   # | Name  |  Part 1 |  Part 2 |   Delta | Points |
+| --: | ----- | ------: | ------: | ------: | -----: |
+|   1 | Alice | 0:10:00 | 0:11:40 | 0:01:40 |      8 |
+|   2 | Bob   | 0:16:40 |         |         |      3 |
+"
+        );
+    }
+
+    #[test]
+    fn test_bad_json() {
+        assert!(Leaderboard::parse("<html>log in</html>").is_err());
+        let board = Leaderboard::parse(r#"{"event":"next","members":{}}"#).unwrap();
+        assert!(board.day(1).is_err());
+    }
+}
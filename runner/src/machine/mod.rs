@@ -0,0 +1,76 @@
+//! Identify the machine a timing came from, so that runs on different
+//! hardware aren't compared with each other.
+use std::{fs, process::Command, thread};
+
+/// 64-bit FNV-1a. Unlike `DefaultHasher`, its output is stable across Rust
+/// releases, so fingerprints in old history files stay meaningful.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn hostname() -> String {
+    fs::read_to_string("/etc/hostname")
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .or_else(|| command_output("hostname", &[]))
+        .unwrap_or_default()
+}
+
+fn cpu_model() -> String {
+    fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|cpuinfo| {
+            cpuinfo
+                .lines()
+                .find(|line| line.starts_with("model name"))
+                .and_then(|line| line.split_once(':'))
+                .map(|(_, model)| model.trim().to_string())
+        })
+        .or_else(|| command_output("sysctl", &["-n", "machdep.cpu.brand_string"]))
+        .unwrap_or_default()
+}
+
+/// A short hex identifier for this machine: its hostname, CPU model, core
+/// count, OS and architecture.
+pub fn fingerprint() -> String {
+    let cores = thread::available_parallelism().map_or(0, std::num::NonZeroUsize::get);
+    let description = [
+        hostname(),
+        cpu_model(),
+        cores.to_string(),
+        std::env::consts::OS.to_string(),
+        std::env::consts::ARCH.to_string(),
+    ]
+    .join("\n");
+    format!("{:016x}", fnv1a(description.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        let fingerprint = fingerprint();
+        assert_eq!(fingerprint.len(), 16);
+        assert_eq!(fingerprint, super::fingerprint());
+    }
+}
@@ -0,0 +1,864 @@
+//! `aoc`: build and run each puzzle's solution, check its answers, keep a
+//! history of how long each part takes, and report on how that changes over
+//! time.
+#![warn(clippy::pedantic)]
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aoc::{
+    err,
+    solution::{Report, Stage},
+    store::{Answers, Puzzle, Revealed, Secret, Verdict},
+    Result,
+};
+use clap::{Args, Parser, Subcommand};
+
+mod external;
+mod history;
+mod leaderboard;
+mod machine;
+mod perf;
+mod puzzle;
+mod sandbox;
+mod site;
+mod status;
+mod trace;
+mod workspace;
+
+use history::{Entry, History};
+use perf::{format_nanos, stage_label};
+use sandbox::{Failure, Limits};
+use workspace::Workspace;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Run and time Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Which puzzles a command applies to.
+#[derive(Args)]
+struct Selection {
+    /// Only puzzles from this year
+    #[arg(short, long)]
+    year: Option<u16>,
+    /// Only these days (repeatable)
+    #[arg(short, long = "day")]
+    days: Vec<u8>,
+}
+
+impl Selection {
+    fn matches(&self, puzzle: Puzzle) -> bool {
+        self.year.is_none_or(|year| year == puzzle.year)
+            && (self.days.is_empty() || self.days.contains(&puzzle.day))
+    }
+}
+
+/// How long and how much memory each solver process gets.
+#[derive(Args)]
+struct LimitArgs {
+    /// Seconds each part may take before it's stopped
+    #[arg(long, value_name = "SECS", default_value_t = 60.0)]
+    timeout: f64,
+    /// Megabytes of memory each part may map, or 0 for no limit
+    #[arg(long, value_name = "MB", default_value_t = 4096)]
+    memory: u64,
+}
+
+impl LimitArgs {
+    fn limits(&self) -> Result<Limits> {
+        Ok(Limits {
+            timeout: Duration::try_from_secs_f64(self.timeout)
+                .map_err(|e| err!("bad --timeout {}: {}", self.timeout, e))?,
+            memory: (self.memory > 0).then_some(self.memory << 20),
+        })
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Build and run puzzles, checking answers and recording how long each
+    /// part takes
+    Run {
+        #[command(flatten)]
+        selection: Selection,
+        /// Run each puzzle this many times and record the fastest time of
+        /// each part
+        #[arg(short = 'n', long, default_value_t = 1)]
+        samples: u32,
+        /// Don't add the timings to the history
+        #[arg(long)]
+        no_record: bool,
+        /// Store answers to parts that don't have a known answer yet
+        #[arg(long)]
+        save: bool,
+        /// Build with tracing and show the events each part traces; timings
+        /// aren't recorded
+        #[arg(long)]
+        trace: bool,
+        /// Build with tracing and write the events to this file as JSON lines
+        #[arg(long, value_name = "PATH")]
+        trace_json: Option<PathBuf>,
+        /// Only run this part, 1 or 2
+        #[arg(short, long, value_parser = part_arg)]
+        part: Option<Stage>,
+        /// Override one of the puzzle's parameters (repeatable), which needs
+        /// a single puzzle selected; answers aren't checked and timings
+        /// aren't recorded
+        #[arg(long = "param", value_name = "NAME=VALUE", conflicts_with = "save")]
+        params: Vec<String>,
+        #[command(flatten)]
+        limits: LimitArgs,
+    },
+    /// Run the Rust solutions and the external solvers in `solvers.toml`
+    /// side by side, checking every answer
+    Compare {
+        #[command(flatten)]
+        selection: Selection,
+        /// Only run these solvers (repeatable); `rust` is the day binaries
+        #[arg(short, long = "solver", value_name = "NAME")]
+        solvers: Vec<String>,
+        #[command(flatten)]
+        limits: LimitArgs,
+    },
+    /// Show timing trends from the history and flag parts that got slower
+    Perf {
+        #[command(flatten)]
+        selection: Selection,
+        /// How many percent slower than the best or previous run counts as a
+        /// regression
+        #[arg(short, long, default_value_t = 10.0)]
+        threshold: f64,
+        /// Include runs from every machine, not just this one
+        #[arg(long)]
+        all_machines: bool,
+        /// Exit with an error if any part regressed
+        #[arg(long)]
+        fail: bool,
+    },
+    /// Show how far along each day of a year is, as a calendar
+    Status {
+        /// Which year to show
+        #[arg(short, long, default_value_t = 2022)]
+        year: u16,
+        /// Run each day's tests to see whether they pass
+        #[arg(long)]
+        test: bool,
+        /// Render as a Markdown table
+        #[arg(long)]
+        markdown: bool,
+    },
+    /// Write a day's puzzle description to its `puzzle.md` as Markdown,
+    /// converted from its page on Advent of Code
+    Puzzle {
+        /// Which year the day is from
+        #[arg(short, long, default_value_t = 2022)]
+        year: u16,
+        /// Which day
+        #[arg(short, long)]
+        day: u8,
+        /// Read the page from this saved HTML file instead of fetching it
+        #[arg(short, long, value_name = "PATH")]
+        file: Option<PathBuf>,
+    },
+    /// Generate a static HTML site with a page per day: its puzzle text,
+    /// highlighted solution, verified answers and latest timings
+    Site {
+        #[command(flatten)]
+        selection: Selection,
+        /// Where to write the site
+        #[arg(short, long, default_value = "target/site")]
+        out: PathBuf,
+    },
+    /// Show the stored answers, which needs the secret they were sealed with
+    Reveal {
+        #[command(flatten)]
+        selection: Selection,
+    },
+    /// Rewrite a plain-text `answers.tsv` so it holds digests instead,
+    /// sealing the answers with the local secret if there is one
+    Seal,
+    /// Show the standings of a private leaderboard, or everyone's times on
+    /// one day
+    Leaderboard {
+        /// The leaderboard's id, which is its owner's user id; defaults to
+        /// `AOC_LEADERBOARD`
+        #[arg(long, env = "AOC_LEADERBOARD")]
+        id: Option<u64>,
+        /// Which year's leaderboard to fetch
+        #[arg(short, long, default_value_t = 2022)]
+        year: u16,
+        /// Read the leaderboard JSON from this file instead of fetching it
+        #[arg(short, long, value_name = "PATH", conflicts_with = "id")]
+        file: Option<PathBuf>,
+        /// Show each member's times on this day instead of the standings
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// Leave these days out of the scores (repeatable)
+        #[arg(long = "exclude-day", value_name = "DAY")]
+        excluded: Vec<u8>,
+        /// Render as a Markdown table
+        #[arg(long)]
+        markdown: bool,
+    },
+}
+
+fn history(workspace: &Workspace) -> History {
+    History::new(workspace.state_dir().join("history.tsv"))
+}
+
+fn secret_path(workspace: &Workspace) -> PathBuf {
+    workspace.state_dir().join("secret")
+}
+
+/// The workspace's answers, unlocked with the local secret if there is one.
+/// With `create`, a missing secret is generated so new answers get sealed.
+fn answers(workspace: &Workspace, create: bool) -> Result<Answers> {
+    let path = secret_path(workspace);
+    let secret = match Secret::load(&path)? {
+        None if create => {
+            eprintln!(
+                "created {}; keep a copy to reveal saved answers elsewhere",
+                path.display()
+            );
+            Some(Secret::create(&path)?)
+        }
+        secret => secret,
+    };
+    Answers::load(&workspace.answers_path(), secret)
+}
+
+fn part_arg(part: &str) -> std::result::Result<Stage, String> {
+    match part.parse() {
+        Ok(Stage::Part1) => Ok(Stage::Part1),
+        Ok(Stage::Part2) => Ok(Stage::Part2),
+        _ => Err("expected 1 or 2".to_string()),
+    }
+}
+
+/// Run one part of a puzzle `samples` times, each in its own process, and
+/// keep the fastest time for parsing and the part.
+fn run_part(
+    workspace: &Workspace,
+    puzzle: Puzzle,
+    part: Stage,
+    samples: u32,
+    trace: bool,
+    params: &[String],
+    limits: Limits,
+) -> Result<Vec<Report>> {
+    let mut fastest = workspace.run(puzzle, part, trace, params, limits)?;
+    for _ in 1..samples {
+        let reports = workspace.run(puzzle, part, trace, params, limits)?;
+        for (best, report) in fastest.iter_mut().zip(reports) {
+            best.elapsed = best.elapsed.min(report.elapsed);
+        }
+    }
+    Ok(fastest)
+}
+
+/// Run each of `parts` of a puzzle in its own process, returning the result of
+/// each stage in order. Each part parses the input again; the fastest parse
+/// is kept, with the events traced by only the first.
+fn run_puzzle(
+    workspace: &Workspace,
+    puzzle: Puzzle,
+    parts: &[Stage],
+    samples: u32,
+    trace: bool,
+    params: &[String],
+    limits: Limits,
+) -> Vec<(Stage, Result<Report>)> {
+    let mut parse: Option<Report> = None;
+    let mut results = Vec::new();
+    for &part in parts {
+        match run_part(
+            workspace,
+            puzzle,
+            part,
+            samples.max(1),
+            trace,
+            params,
+            limits,
+        ) {
+            Ok(reports) => {
+                for report in reports {
+                    match &mut parse {
+                        _ if report.stage != Stage::Parse => results.push((part, Ok(report))),
+                        Some(best) => best.elapsed = best.elapsed.min(report.elapsed),
+                        None => parse = Some(report),
+                    }
+                }
+            }
+            Err(e) => results.push((part, Err(e))),
+        }
+    }
+    parse
+        .map(|report| (Stage::Parse, Ok(report)))
+        .into_iter()
+        .chain(results)
+        .collect()
+}
+
+/// Where `aoc run` sends the events that puzzles trace.
+struct TraceOutput {
+    show: bool,
+    json: Option<BufWriter<File>>,
+}
+
+impl TraceOutput {
+    fn new(show: bool, json: Option<&Path>) -> Result<Option<Self>> {
+        if !show && json.is_none() {
+            return Ok(None);
+        }
+        let json = json.map(File::create).transpose()?.map(BufWriter::new);
+        Ok(Some(Self { show, json }))
+    }
+
+    fn write(&mut self, puzzle: Puzzle, stage: Stage, events: &[String]) -> Result<()> {
+        for event in events {
+            if self.show {
+                println!("    {}", trace::format_event(event)?);
+            }
+            if let Some(json) = &mut self.json {
+                writeln!(json, "{}", trace::json_line(puzzle, stage, event)?)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error unless some puzzles are selected, and only one if there are
+/// `params`, as parameters are specific to one puzzle.
+fn check_selection(puzzles: &[Puzzle], params: &[String]) -> Result<()> {
+    match puzzles {
+        [] => Err(err!("no puzzles in the workspace match")),
+        _ if params.is_empty() => Ok(()),
+        [_] => Ok(()),
+        _ => Err(err!(
+            "--param applies to a single puzzle, but {} are selected; pick one with --day and --year",
+            puzzles.len()
+        )),
+    }
+}
+
+/// Run the selected puzzles. With `params`, the answers are for other
+/// inputs than the stored ones, so they aren't checked, and the timings
+/// aren't recorded.
+#[allow(clippy::too_many_arguments)]
+fn run(
+    selection: &Selection,
+    parts: &[Stage],
+    samples: u32,
+    record: bool,
+    save: bool,
+    params: &[String],
+    limits: Limits,
+    mut trace: Option<TraceOutput>,
+) -> Result<()> {
+    let workspace = Workspace::locate();
+    let puzzles: Vec<_> = workspace
+        .puzzles()?
+        .into_iter()
+        .filter(|&puzzle| selection.matches(puzzle))
+        .collect();
+    check_selection(&puzzles, params)?;
+    let tracing = trace.is_some();
+    workspace.build(&puzzles, tracing)?;
+
+    let mut answers = answers(&workspace, save)?;
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let commit = workspace.commit();
+    let machine = machine::fingerprint();
+    let mut entries = Vec::new();
+    let mut failures: BTreeMap<&str, usize> = BTreeMap::new();
+    let (mut wrong, mut saved) = (0, 0);
+
+    for puzzle in puzzles {
+        let results = run_puzzle(&workspace, puzzle, parts, samples, tracing, params, limits);
+        for (stage, result) in results {
+            let report = match result {
+                Ok(report) => report,
+                Err(e) => {
+                    let kind = e.downcast_ref::<Failure>().map_or("error", Failure::kind);
+                    *failures.entry(kind).or_default() += 1;
+                    println!("{} {:<6}  {}", puzzle, stage_label(stage), e);
+                    continue;
+                }
+            };
+            let nanos = report.elapsed.as_nanos();
+            let answer = report.answer.unwrap_or_default();
+            let verdict = if report.stage == Stage::Parse || !params.is_empty() {
+                String::new()
+            } else {
+                match answers.check(puzzle, report.stage, &answer) {
+                    Verdict::Correct => "ok".to_string(),
+                    Verdict::Wrong { expected } => {
+                        wrong += 1;
+                        match expected {
+                            Some(expected) => format!("WRONG, expected {expected}"),
+                            None => "WRONG".to_string(),
+                        }
+                    }
+                    Verdict::Unknown if save => {
+                        answers.insert(puzzle, report.stage, &answer)?;
+                        saved += 1;
+                        "saved".to_string()
+                    }
+                    Verdict::Unknown => String::new(),
+                }
+            };
+            let line = format!(
+                "{} {:<6}  {:<20} {:>9}  {}",
+                puzzle,
+                stage_label(report.stage),
+                answer,
+                format_nanos(nanos),
+                verdict
+            );
+            println!("{}", line.trim_end());
+            if let Some(trace) = &mut trace {
+                trace.write(puzzle, report.stage, &report.trace)?;
+            }
+            entries.push(Entry {
+                timestamp,
+                commit: commit.clone(),
+                machine: machine.clone(),
+                puzzle,
+                stage: report.stage,
+                nanos,
+            });
+        }
+    }
+
+    if let Some(TraceOutput {
+        json: Some(json), ..
+    }) = &mut trace
+    {
+        json.flush()?;
+    }
+    if record && !tracing && params.is_empty() {
+        history(&workspace).append(&entries)?;
+    }
+    if saved > 0 {
+        answers.save(&workspace.answers_path())?;
+    }
+    let failed: usize = failures.values().sum();
+    if failed > 0 || wrong > 0 {
+        let kinds: Vec<_> = failures
+            .iter()
+            .map(|(kind, count)| format!("{count} {kind}"))
+            .collect();
+        return Err(err!(
+            "{} part(s) failed ({}), {} wrong answer(s)",
+            failed,
+            kinds.join(", "),
+            wrong
+        ));
+    }
+    Ok(())
+}
+
+fn perf(selection: &Selection, threshold: f64, all_machines: bool, fail: bool) -> Result<()> {
+    let workspace = Workspace::locate();
+    let history = history(&workspace);
+    let machine = machine::fingerprint();
+    let entries: Vec<Entry> = history
+        .load()?
+        .into_iter()
+        .filter(|entry| all_machines || entry.machine == machine)
+        .filter(|entry| selection.matches(entry.puzzle))
+        .collect();
+    if entries.is_empty() {
+        println!(
+            "no timings in {} for this machine yet, try `aoc run`",
+            history.path().display()
+        );
+        return Ok(());
+    }
+
+    let trends = perf::trends(&entries);
+    let (table, regressed) = perf::report(&trends, threshold);
+    if all_machines {
+        println!("all machines, threshold {threshold}%\n");
+    } else {
+        println!("machine {machine}, threshold {threshold}%\n");
+    }
+    print!("{table}");
+    if regressed > 0 {
+        println!("\n{regressed} part(s) slower than {threshold}% over their best or previous run");
+        if fail {
+            return Err(err!("{} regression(s)", regressed));
+        }
+    }
+    Ok(())
+}
+
+/// Each part of `puzzle` as solved by the Rust day (if `rust`) and by each of
+/// `solvers` that does that day, in order of part.
+fn compare_puzzle<'a>(
+    workspace: &Workspace,
+    puzzle: Puzzle,
+    rust: bool,
+    solvers: &'a [external::Solver],
+    limits: Limits,
+) -> Vec<(&'a str, Stage, Result<Report>)> {
+    let mut results: Vec<(&str, Stage, Result<Report>)> = Vec::new();
+    if rust {
+        let parts = [Stage::Part1, Stage::Part2];
+        for (stage, result) in run_puzzle(workspace, puzzle, &parts, 1, false, &[], limits) {
+            if stage != Stage::Parse {
+                results.push(("rust", stage, result));
+            }
+        }
+    }
+    for solver in solvers.iter().filter(|solver| solver.solves(puzzle)) {
+        match solver.run(workspace.root(), puzzle, limits) {
+            Ok(reports) => results.extend(
+                reports
+                    .into_iter()
+                    .map(|report| (solver.name.as_str(), report.stage, Ok(report))),
+            ),
+            Err(e) => {
+                let message = e.to_string();
+                for stage in [Stage::Part1, Stage::Part2] {
+                    results.push((solver.name.as_str(), stage, Err(err!("{}", message))));
+                }
+            }
+        }
+    }
+    results.sort_by_key(|&(_, stage, _)| stage);
+    results
+}
+
+fn compare(selection: &Selection, only: &[String], limits: Limits) -> Result<()> {
+    let workspace = Workspace::locate();
+    let wanted = |name: &str| only.is_empty() || only.iter().any(|only| only == name);
+    let solvers = external::load(&workspace.solvers_path())?;
+    if let Some(name) = only
+        .iter()
+        .find(|&name| name != "rust" && !solvers.iter().any(|solver| &solver.name == name))
+    {
+        return Err(err!(
+            "no solver named {} in {}",
+            name,
+            workspace.solvers_path().display()
+        ));
+    }
+    let solvers: Vec<_> = solvers
+        .into_iter()
+        .filter(|solver| wanted(&solver.name))
+        .collect();
+    let rust = wanted("rust");
+    let puzzles: Vec<_> = workspace
+        .puzzles()?
+        .into_iter()
+        .filter(|&puzzle| selection.matches(puzzle))
+        .filter(|&puzzle| rust || solvers.iter().any(|solver| solver.solves(puzzle)))
+        .collect();
+    if puzzles.is_empty() {
+        return Err(err!(
+            "no puzzles match; external solvers are set up in {}",
+            workspace.solvers_path().display()
+        ));
+    }
+    if rust {
+        workspace.build(&puzzles, false)?;
+    }
+    let answers = answers(&workspace, false)?;
+    let (mut failed, mut wrong) = (0, 0);
+
+    for puzzle in puzzles {
+        let results = compare_puzzle(&workspace, puzzle, rust, &solvers, limits);
+
+        for (name, stage, result) in results {
+            let report = match result {
+                Ok(report) => report,
+                Err(e) => {
+                    failed += 1;
+                    println!("{} {:<6}  {:<8} {}", puzzle, stage_label(stage), name, e);
+                    continue;
+                }
+            };
+            let answer = report.answer.unwrap_or_default();
+            let verdict = match answers.check(puzzle, stage, &answer) {
+                Verdict::Correct => "ok",
+                Verdict::Wrong { .. } => {
+                    wrong += 1;
+                    "WRONG"
+                }
+                Verdict::Unknown => "",
+            };
+            let line = format!(
+                "{} {:<6}  {:<8} {:<20} {:>9}  {}",
+                puzzle,
+                stage_label(stage),
+                name,
+                answer,
+                format_nanos(report.elapsed.as_nanos()),
+                verdict
+            );
+            println!("{}", line.trim_end());
+        }
+    }
+
+    if failed > 0 || wrong > 0 {
+        return Err(err!("{} part(s) failed, {} wrong answer(s)", failed, wrong));
+    }
+    Ok(())
+}
+
+fn status(year: u16, test: bool, markdown: bool) -> Result<()> {
+    let workspace = Workspace::locate();
+    let answers = answers(&workspace, false)?;
+    let members = workspace.puzzles()?;
+    let machine = machine::fingerprint();
+    let entries: Vec<Entry> = history(&workspace)
+        .load()?
+        .into_iter()
+        .filter(|entry| entry.machine == machine && entry.puzzle.year == year)
+        .collect();
+    let times = status::latest_times(&entries);
+
+    let mut days = Vec::new();
+    for day in 1..=25 {
+        let puzzle = Puzzle::new(year, day)?;
+        let dir = workspace.root().join(puzzle.member());
+        let lib = dir
+            .is_dir()
+            .then(|| std::fs::read_to_string(dir.join("src").join("lib.rs")))
+            .transpose()?;
+        let progress = status::Progress::of(
+            lib.as_deref(),
+            [Stage::Part1, Stage::Part2].map(|part| answers.contains(puzzle, part)),
+        );
+        let member = members.contains(&puzzle);
+        let tests = if test && member {
+            eprintln!("testing {puzzle}");
+            Some(workspace.test(puzzle)?)
+        } else {
+            None
+        };
+        days.push(status::DayStatus {
+            puzzle,
+            progress,
+            member,
+            tests,
+            nanos: times.get(&puzzle).copied(),
+        });
+    }
+    if markdown {
+        print!("{}", status::render_markdown(&days));
+    } else {
+        print!("{}", status::render_calendar(year, &days));
+    }
+    Ok(())
+}
+
+fn write_puzzle(year: u16, day: u8, file: Option<&Path>) -> Result<()> {
+    let puzzle = Puzzle::new(year, day)?;
+    let dir = Workspace::locate().root().join(puzzle.member());
+    if !dir.is_dir() {
+        return Err(err!(
+            "{} doesn't exist; scaffold it with create.sh first",
+            dir.display()
+        ));
+    }
+    let html = match file {
+        Some(file) => std::fs::read_to_string(file)?,
+        None => puzzle::fetch(puzzle)?,
+    };
+    let path = dir.join("puzzle.md");
+    puzzle::write(&path, puzzle, &html)?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+fn site(selection: &Selection, out: &Path) -> Result<()> {
+    let workspace = Workspace::locate();
+    let answers = answers(&workspace, false)?;
+    let machine = machine::fingerprint();
+    let entries: Vec<Entry> = history(&workspace)
+        .load()?
+        .into_iter()
+        .filter(|entry| entry.machine == machine)
+        .collect();
+    let trends = perf::trends(&entries);
+
+    let mut days = Vec::new();
+    for puzzle in workspace.puzzles()? {
+        if !selection.matches(puzzle) {
+            continue;
+        }
+        let dir = workspace.root().join(puzzle.member());
+        let read = |path: PathBuf| {
+            path.is_file()
+                .then(|| std::fs::read_to_string(path))
+                .transpose()
+        };
+        days.push(site::Day {
+            puzzle,
+            text: read(dir.join("puzzle.md"))?,
+            source: read(dir.join("src").join("lib.rs"))?,
+            verified: [Stage::Part1, Stage::Part2].map(|part| answers.contains(puzzle, part)),
+            timings: trends
+                .iter()
+                .filter(|trend| trend.puzzle == puzzle)
+                .filter_map(|trend| Some((trend.stage, trend.latest()?, trend.fastest()?)))
+                .collect(),
+        });
+    }
+    let out = workspace.root().join(out);
+    site::write(&out, &days)?;
+    println!("wrote {} page(s) to {}", days.len() + 1, out.display());
+    Ok(())
+}
+
+fn reveal(selection: &Selection) -> Result<()> {
+    let workspace = Workspace::locate();
+    let answers = answers(&workspace, false)?;
+    if answers.secret().is_none() {
+        return Err(err!(
+            "revealing answers needs the secret in {} or {}",
+            secret_path(&workspace).display(),
+            Secret::ENV
+        ));
+    }
+    for (puzzle, part) in answers
+        .keys()
+        .filter(|&(puzzle, _)| selection.matches(puzzle))
+    {
+        let answer = match answers.reveal(puzzle, part) {
+            Some(Revealed::Answer(answer)) => answer,
+            Some(Revealed::Unsealed) => "(saved without a secret)".to_string(),
+            Some(Revealed::OtherSecret) | None => "(sealed with another secret)".to_string(),
+        };
+        println!("{} {:<6}  {}", puzzle, stage_label(part), answer);
+    }
+    Ok(())
+}
+
+fn seal() -> Result<()> {
+    let workspace = Workspace::locate();
+    let answers = answers(&workspace, false)?;
+    if !answers.is_plain() {
+        println!("{} is already sealed", workspace.answers_path().display());
+        return Ok(());
+    }
+    answers.save(&workspace.answers_path())?;
+    println!("sealed {} answer(s)", answers.keys().count());
+    if answers.secret().is_none() {
+        println!(
+            "without a secret in {} or {}, they can be checked but not revealed",
+            secret_path(&workspace).display(),
+            Secret::ENV
+        );
+    }
+    Ok(())
+}
+
+fn show_leaderboard(
+    id: Option<u64>,
+    year: u16,
+    file: Option<&Path>,
+    day: Option<u8>,
+    excluded: &[u8],
+    markdown: bool,
+) -> Result<()> {
+    let json = match (file, id) {
+        (Some(file), _) => std::fs::read_to_string(file)?,
+        (None, Some(id)) => {
+            let cache = Workspace::locate()
+                .state_dir()
+                .join("leaderboard")
+                .join(format!("{year}-{id}.json"));
+            leaderboard::load_cached(&cache, year, id)?
+        }
+        (None, None) => return Err(err!("pass --id, set AOC_LEADERBOARD, or pass --file")),
+    };
+    let board = leaderboard::Leaderboard::parse(&json)?;
+    match day {
+        Some(day) => print!("{}", leaderboard::render_day(&board, day, markdown)?),
+        None => print!(
+            "{}",
+            leaderboard::render_standings(&board, excluded, markdown)
+        ),
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Run {
+            selection,
+            samples,
+            no_record,
+            save,
+            trace,
+            trace_json,
+            part,
+            params,
+            limits,
+        } => run(
+            &selection,
+            &part.map_or(vec![Stage::Part1, Stage::Part2], |part| vec![part]),
+            samples,
+            !no_record,
+            save,
+            &params,
+            limits.limits()?,
+            TraceOutput::new(trace, trace_json.as_deref())?,
+        ),
+        Command::Perf {
+            selection,
+            threshold,
+            all_machines,
+            fail,
+        } => perf(&selection, threshold, all_machines, fail),
+        Command::Compare {
+            selection,
+            solvers,
+            limits,
+        } => compare(&selection, &solvers, limits.limits()?),
+        Command::Status {
+            year,
+            test,
+            markdown,
+        } => status(year, test, markdown),
+        Command::Puzzle { year, day, file } => write_puzzle(year, day, file.as_deref()),
+        Command::Site { selection, out } => site(&selection, &out),
+        Command::Reveal { selection } => reveal(&selection),
+        Command::Seal => seal(),
+        Command::Leaderboard {
+            id,
+            year,
+            file,
+            day,
+            excluded,
+            markdown,
+        } => show_leaderboard(id, year, file.as_deref(), day, &excluded, markdown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_selection() {
+        let one = [Puzzle::new(2022, 6).unwrap()];
+        let two = [one[0], Puzzle::new(2022, 7).unwrap()];
+        let params = ["packet=14".to_string()];
+        assert!(check_selection(&one, &params).is_ok());
+        assert!(check_selection(&two, &[]).is_ok());
+        assert!(check_selection(&[], &[]).is_err());
+        let err = check_selection(&two, &params).unwrap_err();
+        assert!(err.to_string().contains("2 are selected"));
+    }
+}
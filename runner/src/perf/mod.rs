@@ -0,0 +1,273 @@
+//! Summarise the timing history per puzzle and stage, and flag stages whose
+//! latest run is slower than their best or previous run.
+use std::{collections::BTreeMap, fmt::Write};
+
+use aoc::{solution::Stage, store::Puzzle};
+
+use crate::history::Entry;
+
+/// How many of the most recent runs the trend sparkline shows.
+const TREND_RUNS: usize = 12;
+
+/// A human-friendly duration, e.g. `101.6µs`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_nanos(nanos: u128) -> String {
+    let (scale, unit) = match nanos {
+        0..=999 => return format!("{nanos}ns"),
+        1_000..=999_999 => (1e3, "µs"),
+        1_000_000..=999_999_999 => (1e6, "ms"),
+        _ => (1e9, "s"),
+    };
+    format!("{:.1}{}", nanos as f64 / scale, unit)
+}
+
+/// How the runner labels a stage, e.g. `part 1`.
+pub fn stage_label(stage: Stage) -> &'static str {
+    match stage {
+        Stage::Parse => "parse",
+        Stage::Part1 => "part 1",
+        Stage::Part2 => "part 2",
+    }
+}
+
+/// A bar per value, scaled between the smallest and largest.
+fn sparkline(values: &[u128]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    values
+        .iter()
+        .map(|&value| {
+            let idx = if max == min {
+                0
+            } else {
+                (value - min) * 7 / (max - min)
+            };
+            BARS[usize::try_from(idx).unwrap_or(7)]
+        })
+        .collect()
+}
+
+/// What the latest run is compared against.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Baseline {
+    Best,
+    Previous,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Regression {
+    pub baseline: Baseline,
+    /// How much slower the latest run is, e.g. `25.0` for 25%.
+    pub percent: f64,
+}
+
+/// Every recorded time of one stage of one puzzle, oldest first.
+#[derive(Debug, PartialEq)]
+pub struct Trend<'a> {
+    pub puzzle: Puzzle,
+    pub stage: Stage,
+    pub runs: Vec<&'a Entry>,
+}
+
+impl Trend<'_> {
+    pub fn latest(&self) -> Option<u128> {
+        self.runs.last().map(|run| run.nanos)
+    }
+
+    /// The fastest run, the latest included.
+    pub fn fastest(&self) -> Option<u128> {
+        self.runs.iter().map(|run| run.nanos).min()
+    }
+
+    /// The run before the latest.
+    fn previous(&self) -> Option<u128> {
+        self.runs.iter().rev().nth(1).map(|run| run.nanos)
+    }
+
+    /// The fastest run before the latest.
+    fn best(&self) -> Option<u128> {
+        self.runs
+            .split_last()
+            .and_then(|(_, earlier)| earlier.iter().map(|run| run.nanos).min())
+    }
+
+    /// The baselines that the latest run is more than `threshold` percent
+    /// slower than.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn regressions(&self, threshold: f64) -> Vec<Regression> {
+        let Some(latest) = self.latest() else {
+            return Vec::new();
+        };
+        [
+            (Baseline::Best, self.best()),
+            (Baseline::Previous, self.previous()),
+        ]
+        .into_iter()
+        .filter_map(|(baseline, nanos)| {
+            let nanos = nanos.filter(|&nanos| nanos > 0)? as f64;
+            let percent = (latest as f64 - nanos) / nanos * 100.0;
+            (percent > threshold).then_some(Regression { baseline, percent })
+        })
+        .collect()
+    }
+}
+
+/// Group entries by puzzle and stage, keeping the order they were recorded in.
+pub fn trends(entries: &[Entry]) -> Vec<Trend<'_>> {
+    let mut grouped: BTreeMap<(Puzzle, Stage), Vec<&Entry>> = BTreeMap::new();
+    for entry in entries {
+        grouped
+            .entry((entry.puzzle, entry.stage))
+            .or_default()
+            .push(entry);
+    }
+    grouped
+        .into_iter()
+        .map(|((puzzle, stage), runs)| Trend {
+            puzzle,
+            stage,
+            runs,
+        })
+        .collect()
+}
+
+/// A table of trends with regressions flagged, and the number of trends that
+/// regressed.
+pub fn report(trends: &[Trend<'_>], threshold: f64) -> (String, usize) {
+    let mut table = format!(
+        "{:<4} {:<3} {:<6} {:>4} {:>9} {:>9} {:>9} {:<12}  {:<12} flags\n",
+        "year", "day", "stage", "runs", "best", "previous", "latest", "commit", "trend"
+    );
+    let mut regressed = 0;
+    let maybe = |nanos: Option<u128>| nanos.map_or_else(|| "-".to_string(), format_nanos);
+    for trend in trends {
+        let regressions = trend.regressions(threshold);
+        if !regressions.is_empty() {
+            regressed += 1;
+        }
+        let flags: Vec<_> = regressions
+            .iter()
+            .map(|Regression { baseline, percent }| {
+                let baseline = match baseline {
+                    Baseline::Best => "best",
+                    Baseline::Previous => "previous",
+                };
+                format!("+{percent:.1}% vs {baseline}")
+            })
+            .collect();
+        let recent: Vec<_> = trend.runs.iter().map(|run| run.nanos).collect();
+        let recent = &recent[recent.len().saturating_sub(TREND_RUNS)..];
+        let _ = writeln!(
+            table,
+            "{:<4} {:<3} {:<6} {:>4} {:>9} {:>9} {:>9} {:<12}  {:<12} {}",
+            trend.puzzle.year,
+            format!("{:02}", trend.puzzle.day),
+            stage_label(trend.stage),
+            trend.runs.len(),
+            maybe(trend.best()),
+            maybe(trend.previous()),
+            maybe(trend.latest()),
+            trend.runs.last().map_or("", |run| run.commit.as_str()),
+            sparkline(recent),
+            flags.join(", ")
+        );
+    }
+    (table, regressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(day: u8, stage: Stage, times: &[u128]) -> Vec<Entry> {
+        times
+            .iter()
+            .enumerate()
+            .map(|(idx, &nanos)| Entry {
+                timestamp: idx as u64,
+                commit: format!("c{idx}"),
+                machine: "m".into(),
+                puzzle: Puzzle::new(2022, day).unwrap(),
+                stage,
+                nanos,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_format_nanos() {
+        assert_eq!(format_nanos(999), "999ns");
+        assert_eq!(format_nanos(101_618), "101.6µs");
+        assert_eq!(format_nanos(2_500_000), "2.5ms");
+        assert_eq!(format_nanos(12_340_000_000), "12.3s");
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[5, 5]), "▁▁");
+        assert_eq!(sparkline(&[0, 7, 14, 70]), "▁▁▂█");
+    }
+
+    #[test]
+    fn test_trends_group_by_puzzle_and_stage() {
+        let mut history = entries(2, Stage::Part1, &[1, 2]);
+        history.extend(entries(1, Stage::Part2, &[3]));
+        history.extend(entries(2, Stage::Part1, &[4]));
+        let trends = trends(&history);
+        let summary: Vec<_> = trends
+            .iter()
+            .map(|t| {
+                (
+                    t.puzzle.day,
+                    t.stage,
+                    t.runs.iter().map(|r| r.nanos).collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(1, Stage::Part2, vec![3]), (2, Stage::Part1, vec![1, 2, 4])]
+        );
+    }
+
+    #[test]
+    fn test_regressions() {
+        let history = entries(1, Stage::Part1, &[100, 200, 130]);
+        let trend = &trends(&history)[0];
+        // 30% slower than the best, but faster than the previous run
+        let regressions = trend.regressions(10.0);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].baseline, Baseline::Best);
+        assert!((regressions[0].percent - 30.0).abs() < 1e-9);
+        assert!(trend.regressions(50.0).is_empty());
+        assert_eq!((trend.latest(), trend.fastest()), (Some(130), Some(100)));
+
+        let history = entries(1, Stage::Part1, &[100, 100, 150]);
+        let baselines: Vec<_> = trends(&history)[0]
+            .regressions(10.0)
+            .iter()
+            .map(|r| r.baseline)
+            .collect();
+        assert_eq!(baselines, vec![Baseline::Best, Baseline::Previous]);
+
+        // A single run has nothing to compare against
+        let history = entries(1, Stage::Part1, &[100]);
+        assert!(trends(&history)[0].regressions(0.0).is_empty());
+    }
+
+    #[test]
+    fn test_report_counts_regressions() {
+        let mut history = entries(1, Stage::Parse, &[10, 10]);
+        history.extend(entries(1, Stage::Part1, &[10, 20]));
+        let (table, regressed) = report(&trends(&history), 10.0);
+        assert_eq!(regressed, 1);
+        assert_eq!(table.lines().count(), 3);
+        assert!(
+            table.contains("+100.0% vs best, +100.0% vs previous"),
+            "{table}"
+        );
+    }
+}
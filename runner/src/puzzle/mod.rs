@@ -0,0 +1,647 @@
+//! A day's puzzle description as Markdown, converted from the page Advent of
+//! Code serves at `/<year>/day/<day>`.
+//!
+//! The description is each `<article class="day-desc">` on the page: part 1,
+//! then part 2 once it's unlocked. Headings become `# Day 3: Sock Drawer`
+//! and `## Part Two`, `<em>` becomes `*emphasis*` (answers included, as in
+//! ``*`42`*``), `<code>` becomes code spans and `<pre>` fenced code blocks.
+//! Everything outside the articles, like the answers already given, is left
+//! out.
+use std::{fmt::Write, fs, path::Path, process::Command};
+
+use aoc::{err, store::Puzzle, Result};
+
+use crate::leaderboard;
+
+/// Elements that never have children or a closing tag.
+const VOID: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "wbr",
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Node {
+    /// Text with its character references decoded.
+    Text(String),
+    Element(Element),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Element {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Node>,
+}
+
+impl Element {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(attr, _)| attr == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn has_class(&self, class: &str) -> bool {
+        self.attr("class")
+            .is_some_and(|classes| classes.split_whitespace().any(|c| c == class))
+    }
+
+    /// The text of every descendant, as it would be displayed.
+    fn text(&self) -> String {
+        let mut text = String::new();
+        for child in &self.children {
+            match child {
+                Node::Text(t) => text.push_str(t),
+                Node::Element(element) => text.push_str(&element.text()),
+            }
+        }
+        text
+    }
+
+    /// Whether any descendant is emphasized.
+    fn has_em(&self) -> bool {
+        self.children.iter().any(|child| match child {
+            Node::Text(_) => false,
+            Node::Element(element) => element.name == "em" || element.has_em(),
+        })
+    }
+
+    /// Every descendant article that holds a puzzle description.
+    fn articles<'a>(&'a self, found: &mut Vec<&'a Element>) {
+        for child in &self.children {
+            if let Node::Element(element) = child {
+                if element.name == "article" && element.has_class("day-desc") {
+                    found.push(element);
+                } else {
+                    element.articles(found);
+                }
+            }
+        }
+    }
+}
+
+/// `text` with its character references decoded; ones it doesn't know are
+/// kept as they are.
+fn decode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').and_then(|end| {
+            let name = &rest[1..end];
+            let c = match name {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                _ => {
+                    let code = name.strip_prefix('#')?;
+                    let code = match code.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => code.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end + 1))
+        });
+        if let Some((c, len)) = decoded {
+            out.push(c);
+            rest = &rest[len..];
+        } else {
+            out.push('&');
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The name and attributes of the tag `tag`, between its `<` and `>`.
+fn parse_tag(tag: &str) -> (String, Vec<(String, String)>) {
+    let tag = tag.trim_end_matches('/');
+    let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+    let name = tag[..name_end].to_ascii_lowercase();
+    let mut attrs = Vec::new();
+    let mut rest = tag[name_end..].trim_start();
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+        let mut value = String::new();
+        if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (raw, len) = if let Some(quote @ ('"' | '\'')) = after.chars().next() {
+                let end = after[1..].find(quote).map_or(after.len(), |end| end + 1);
+                (&after[1..end], (end + 1).min(after.len()))
+            } else {
+                let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                (&after[..end], end)
+            };
+            value = decode(raw);
+            rest = after[len..].trim_start();
+        }
+        attrs.push((key, value));
+    }
+    (name, attrs)
+}
+
+/// A forgiving parse of `html` into a tree under a nameless root: unclosed
+/// elements are closed by their parent's end tag, and stray end tags are
+/// ignored.
+fn parse(html: &str) -> Element {
+    let mut stack = vec![Element::default()];
+    let close = |stack: &mut Vec<Element>| {
+        let element = stack.pop().expect("the root is never closed");
+        stack
+            .last_mut()
+            .expect("the root is never closed")
+            .children
+            .push(Node::Element(element));
+    };
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            let top = stack.last_mut().expect("the root is never closed");
+            top.children.push(Node::Text(decode(rest)));
+            break;
+        };
+        if start > 0 {
+            let top = stack.last_mut().expect("the root is never closed");
+            top.children.push(Node::Text(decode(&rest[..start])));
+        }
+        rest = &rest[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(end) = rest.find('>') else {
+            let top = stack.last_mut().expect("the root is never closed");
+            top.children.push(Node::Text(rest.to_string()));
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim().to_ascii_lowercase();
+            if let Some(open) = stack.iter().skip(1).rposition(|e| e.name == name) {
+                while stack.len() > open + 1 {
+                    close(&mut stack);
+                }
+            }
+        } else if !tag.starts_with('!') && !tag.starts_with('?') {
+            let (name, attrs) = parse_tag(tag);
+            if name == "script" || name == "style" {
+                // Their contents aren't markup, and aren't displayed.
+                let end = format!("</{name}");
+                rest = rest.find(&end).map_or("", |idx| &rest[idx..]);
+                rest = rest.find('>').map_or("", |idx| &rest[idx + 1..]);
+                continue;
+            }
+            let void = tag.ends_with('/') || VOID.contains(&name.as_str());
+            stack.push(Element {
+                name,
+                attrs,
+                children: Vec::new(),
+            });
+            if void {
+                close(&mut stack);
+            }
+        }
+    }
+    while stack.len() > 1 {
+        close(&mut stack);
+    }
+    stack.pop().expect("the root is never closed")
+}
+
+/// `text` with the characters that Markdown would read as formatting
+/// escaped.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '<') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// `text` as a code span, with a longer delimiter if it holds a backtick.
+fn code_span(text: &str) -> String {
+    if text.contains('`') {
+        format!("`` {text} ``")
+    } else {
+        format!("`{text}`")
+    }
+}
+
+/// Push `text` with each run of whitespace collapsed to one space, as a
+/// browser displays it, and none right after a space or line break.
+fn push_collapsed(out: &mut String, text: &str) {
+    for c in text.chars() {
+        if !c.is_whitespace() {
+            out.push(c);
+        } else if !out.ends_with([' ', '\n']) {
+            out.push(' ');
+        }
+    }
+}
+
+/// Wrap `inner` in `*`, keeping any whitespace at its ends outside, where
+/// Markdown needs it.
+fn emphasize(out: &mut String, inner: &str) {
+    let trimmed = inner.trim();
+    if trimmed.is_empty() {
+        push_collapsed(out, inner);
+        return;
+    }
+    let start = inner.len() - inner.trim_start().len();
+    push_collapsed(out, &inner[..start]);
+    out.push('*');
+    out.push_str(trimmed);
+    out.push('*');
+    out.push_str(&inner[start + trimmed.len()..]);
+}
+
+/// Render `nodes` as inline Markdown, with links resolved against `base`.
+/// Whitespace in text is collapsed, but not in code, and a `<br>` is a hard
+/// line break.
+fn inline(nodes: &[Node], base: &str, out: &mut String) {
+    for node in nodes {
+        let element = match node {
+            Node::Text(text) => {
+                push_collapsed(out, &escape(text));
+                continue;
+            }
+            Node::Element(element) => element,
+        };
+        match element.name.as_str() {
+            "em" | "strong" | "b" | "i" => {
+                let mut inner = String::new();
+                inline(&element.children, base, &mut inner);
+                emphasize(out, &inner);
+            }
+            "code" => {
+                let code = code_span(&element.text());
+                if element.has_em() {
+                    emphasize(out, &code);
+                } else {
+                    out.push_str(&code);
+                }
+            }
+            "a" => {
+                let mut inner = String::new();
+                inline(&element.children, base, &mut inner);
+                match element.attr("href") {
+                    Some(href) => {
+                        let _ = write!(out, "[{inner}]({})", resolve(base, href));
+                    }
+                    None => out.push_str(&inner),
+                }
+            }
+            "br" => {
+                out.truncate(out.trim_end_matches(' ').len());
+                out.push_str("  \n");
+            }
+            _ => inline(&element.children, base, out),
+        }
+    }
+}
+
+/// `href` as an absolute URL, for a link on a page in the directory `base`.
+fn resolve(base: &str, href: &str) -> String {
+    if href.contains("://") || href.starts_with('#') || href.starts_with("mailto:") {
+        href.to_string()
+    } else if let Some(path) = href.strip_prefix('/') {
+        format!("https://adventofcode.com/{path}")
+    } else {
+        format!("{base}{href}")
+    }
+}
+
+/// Inline Markdown for `nodes`, without whitespace at its ends.
+fn paragraph(nodes: &[Node], base: &str) -> String {
+    let mut text = String::new();
+    inline(nodes, base, &mut text);
+    text.trim().to_string()
+}
+
+/// The blocks of the `index`th article, part 1 being the 0th.
+fn article(article: &Element, index: usize, base: &str, blocks: &mut Vec<String>) {
+    let mut loose = Vec::new();
+    let flush = |loose: &mut Vec<Node>, blocks: &mut Vec<String>| {
+        let text = paragraph(loose, base);
+        if !text.is_empty() {
+            blocks.push(text);
+        }
+        loose.clear();
+    };
+    for child in &article.children {
+        let Node::Element(element) = child else {
+            loose.push(child.clone());
+            continue;
+        };
+        match element.name.as_str() {
+            "h1" | "h2" | "h3" | "p" | "pre" | "ul" | "ol" | "div" => flush(&mut loose, blocks),
+            _ => {
+                loose.push(child.clone());
+                continue;
+            }
+        }
+        match element.name.as_str() {
+            "h1" | "h2" | "h3" => {
+                let title = paragraph(&element.children, base);
+                let title = title.trim_matches(|c: char| c == '-' || c.is_whitespace());
+                let level = if index == 0 { "#" } else { "##" };
+                blocks.push(format!("{level} {title}"));
+            }
+            "pre" => {
+                let code = element.text();
+                let fence = if code.contains("```") { "~~~" } else { "```" };
+                blocks.push(format!("{fence}\n{}\n{fence}", code.trim_end_matches('\n')));
+            }
+            "ul" | "ol" => {
+                let items: Vec<_> = element
+                    .children
+                    .iter()
+                    .filter_map(|item| match item {
+                        Node::Element(li) if li.name == "li" => Some(paragraph(&li.children, base)),
+                        _ => None,
+                    })
+                    .enumerate()
+                    .map(|(idx, item)| match element.name.as_str() {
+                        "ol" => format!("{}. {item}", idx + 1),
+                        _ => format!("- {item}"),
+                    })
+                    .collect();
+                if !items.is_empty() {
+                    blocks.push(items.join("\n"));
+                }
+            }
+            _ => {
+                let text = paragraph(&element.children, base);
+                if !text.is_empty() {
+                    blocks.push(text);
+                }
+            }
+        }
+    }
+    flush(&mut loose, blocks);
+}
+
+/// The description of `puzzle` in the page `html`, as Markdown.
+pub fn to_markdown(puzzle: Puzzle, html: &str) -> Result<String> {
+    let root = parse(html);
+    let mut articles = Vec::new();
+    root.articles(&mut articles);
+    if articles.is_empty() {
+        return Err(err!(
+            "no puzzle description in the page for {puzzle}; is it unlocked yet?"
+        ));
+    }
+    let base = format!("https://adventofcode.com/{}/day/", puzzle.year);
+    let mut blocks = Vec::new();
+    for (index, element) in articles.into_iter().enumerate() {
+        article(element, index, &base, &mut blocks);
+    }
+    Ok(blocks.join("\n\n") + "\n")
+}
+
+/// The heading of part 2 in descriptions saved before this converter, like
+/// `y2022/d05/puzzle.md`, where it's underlined with a line of `-`.
+const OLD_PART_TWO: &str = "\\--- Part Two ---";
+
+/// Where part 2 starts in `markdown`, if it's there, under either heading.
+fn part_two(markdown: &str) -> Option<usize> {
+    markdown.find("\n## ").map(|idx| idx + 1).or_else(|| {
+        let mut start = 0;
+        markdown.split_inclusive('\n').find_map(|line| {
+            let found = (line.trim_end() == OLD_PART_TWO).then_some(start);
+            start += line.len();
+            found
+        })
+    })
+}
+
+/// Part 2 of `markdown`, which starts at `start`, with an old heading
+/// replaced by `## Part Two`.
+fn section(markdown: &str, start: usize) -> String {
+    let section = &markdown[start..];
+    let Some(body) = section.strip_prefix(OLD_PART_TWO) else {
+        return section.to_string();
+    };
+    let mut lines = body.split_inclusive('\n').skip(1).peekable();
+    if lines
+        .peek()
+        .is_some_and(|line| !line.trim().is_empty() && line.trim().chars().all(|c| c == '-'))
+    {
+        lines.next();
+    }
+    format!("## Part Two\n{}", lines.collect::<String>())
+}
+
+/// `fresh` with part 2 from `existing` if `fresh` doesn't have it, as when
+/// the page was fetched without logging in.
+pub fn merge(existing: &str, fresh: &str) -> String {
+    match (part_two(fresh), part_two(existing)) {
+        (None, Some(start)) => format!("{fresh}\n{}", section(existing, start)),
+        _ => fresh.to_string(),
+    }
+}
+
+/// The page for `puzzle`, fetched with `curl`; it only shows part 2 with
+/// the session cookie of someone who has solved part 1.
+pub fn fetch(puzzle: Puzzle) -> Result<String> {
+    let url = format!(
+        "https://adventofcode.com/{}/day/{}",
+        puzzle.year, puzzle.day
+    );
+    let mut curl = Command::new("curl");
+    curl.args(["--silent", "--show-error", "--fail", "--location"])
+        .args(["--user-agent", "aoc22-rust puzzle fetcher"]);
+    match leaderboard::session() {
+        Ok(session) => {
+            curl.args(["--cookie", &format!("session={session}")]);
+        }
+        Err(e) => eprintln!("fetching without logging in, so without part 2: {e}"),
+    }
+    let output = curl.arg(&url).output()?;
+    if !output.status.success() {
+        return Err(err!(
+            "fetching {} failed: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Convert `html` and write it to `path`, keeping part 2 from what's
+/// already there if `html` doesn't have it.
+pub fn write(path: &Path, puzzle: Puzzle, html: &str) -> Result<()> {
+    let fresh = to_markdown(puzzle, html)?;
+    let markdown = match fs::read_to_string(path) {
+        Ok(existing) => merge(&existing, &fresh),
+        Err(_) => fresh,
+    };
+    fs::write(path, markdown)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART1: &str = include_str!("../../fixtures/day-part1.html");
+    const COMPLETE: &str = include_str!("../../fixtures/day-complete.html");
+
+    const MARKDOWN: &str = "\
+# Day 3: Sock Drawer
+
+The Elves keep their spare socks in a *single drawer*, and the drawer is a mess & nobody can find a pair.
+
+Each line of the list is one sock: a colour and a size, like `red 9`. Two socks pair up if both match:
+
+```
+red 9
+blue 7
+red 9
+<green> 8
+```
+
+In this example, there is *`1`* pair. Any sock whose size is `*` or `a_b` matches nothing.
+
+- Socks are *never* left-footed.
+- See [day 1](https://adventofcode.com/2022/day/1) and [the sock spec](https://example.com/socks).
+
+A sock labelled `9  wide` is still size 9.  
+Mind the gap, though.
+
+Find every pair. *How many pairs of socks are in the drawer?*
+";
+
+    const PART_TWO: &str = "
+## Part Two
+
+It turns out sizes within *one* of each other still fit. With that rule, the example has *`2`* pairs:
+
+```
+red 9
+red 8
+```
+
+*How many pairs are there now?*
+";
+
+    fn puzzle() -> Puzzle {
+        Puzzle::new(2022, 3).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        let markdown = to_markdown(puzzle(), PART1).unwrap();
+        assert_eq!(markdown, MARKDOWN);
+    }
+
+    #[test]
+    fn test_both_parts() {
+        let markdown = to_markdown(puzzle(), COMPLETE).unwrap();
+        let (one, two) = markdown.split_at(part_two(&markdown).unwrap() - 1);
+        assert_eq!(one, MARKDOWN);
+        assert_eq!(two, PART_TWO);
+        // The answers given are outside the articles.
+        assert!(!markdown.contains("412"));
+        assert!(!markdown.contains("Your puzzle answer"));
+    }
+
+    #[test]
+    fn test_merge() {
+        let one = to_markdown(puzzle(), PART1).unwrap();
+        let both = to_markdown(puzzle(), COMPLETE).unwrap();
+        assert_eq!(merge(&both, &one), format!("{one}{PART_TWO}"));
+        assert_eq!(merge(&one, &both), both);
+        assert_eq!(merge("", &one), one);
+    }
+
+    #[test]
+    fn test_merge_old_heading() {
+        let existing = include_str!("../../../y2022/d05/puzzle.md");
+        let start = part_two(existing).unwrap();
+        assert!(existing[start..].starts_with(OLD_PART_TWO));
+        let one = to_markdown(puzzle(), PART1).unwrap();
+        let merged = merge(existing, &one);
+        let two = merged.strip_prefix(&one).unwrap();
+        assert!(two.starts_with(
+            "\n## Part Two\n\nAs you watch the crane operator expertly rearrange the crates"
+        ));
+        assert!(existing.ends_with(&two["\n## Part Two\n".len()..]));
+        assert_eq!(part_two(&merged), Some(one.len() + 1));
+    }
+
+    #[test]
+    fn test_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("puzzle.md");
+        write(&path, puzzle(), COMPLETE).unwrap();
+        write(&path, puzzle(), PART1).unwrap();
+        let markdown = fs::read_to_string(&path).unwrap();
+        assert!(markdown.contains("Find every pair."));
+        assert!(markdown.ends_with(PART_TWO));
+    }
+
+    #[test]
+    fn test_not_unlocked() {
+        let page = "<html><body><main><p>Please don't repeatedly request this endpoint before it unlocks!</p></main></body></html>";
+        assert!(to_markdown(puzzle(), page).is_err());
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(
+            decode("a &lt;b&gt; &amp;amp; &#39;&#x2014;&#X41;"),
+            "a <b> &amp; '—A"
+        );
+        assert_eq!(
+            decode("fish & chips &bogus; &#xzz;"),
+            "fish & chips &bogus; &#xzz;"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let root = parse("<p class='x y' hidden>a<br/>b<em>c</p>d</i><!-- <p> -->");
+        let Node::Element(p) = &root.children[0] else {
+            panic!("expected an element");
+        };
+        assert_eq!(p.name, "p");
+        assert_eq!(p.attr("class"), Some("x y"));
+        assert_eq!(p.attr("hidden"), Some(""));
+        assert!(p.has_class("y") && !p.has_class("x y"));
+        assert_eq!(p.children.len(), 4);
+        assert_eq!(p.text(), "abc");
+        assert!(p.has_em());
+        assert_eq!(root.children[1], Node::Text("d".into()));
+        assert_eq!(root.children.len(), 2);
+    }
+
+    #[test]
+    fn test_inline() {
+        let base = "https://adventofcode.com/2022/day/";
+        let html = |html: &str| paragraph(&parse(html).children, base);
+        assert_eq!(html("a<em> b </em>c"), "a *b* c");
+        assert_eq!(html("a <em>b</em>\n  c<br>\nd"), "a *b* c  \nd");
+        assert_eq!(html("<code>a  b</code>"), "`a  b`");
+        assert_eq!(html("<code>a`b</code>"), "`` a`b ``");
+        assert_eq!(html("1 * 2_3 [x]"), "1 \\* 2\\_3 \\[x\\]");
+        assert_eq!(
+            html("<a href=\"3/input\">input</a>"),
+            "[input](https://adventofcode.com/2022/day/3/input)"
+        );
+    }
+}
@@ -0,0 +1,333 @@
+//! Run a day binary in a child process with a time and memory budget, so a
+//! part that panics, loops forever or allocates without end only fails
+//! itself, and say which of those happened.
+use std::{
+    error,
+    fmt::{self, Display},
+    io::{self, Read, Write},
+    process::{Child, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use aoc::Result;
+
+/// How long a child may run and how much memory it may map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+    pub timeout: Duration,
+    /// Bytes of address space, or `None` for no limit.
+    pub memory: Option<u64>,
+}
+
+/// Why a child didn't finish with an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// It returned an error; all of what it printed, which may take several
+    /// lines for a [`Diagnostic`](aoc::diagnostic::Diagnostic).
+    Error(String),
+    Panic(String),
+    Timeout(Duration),
+    /// An allocation failed under the memory limit, or the kernel killed it.
+    OutOfMemory,
+    /// Killed by a signal for some other reason.
+    Signal(i32),
+}
+
+impl Failure {
+    /// A short label for the kind of failure, for summaries.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Failure::Error(_) => "error",
+            Failure::Panic(_) => "panic",
+            Failure::Timeout(_) => "timeout",
+            Failure::OutOfMemory => "out of memory",
+            Failure::Signal(_) => "signal",
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Error(message) => write!(f, "ERROR  {message}"),
+            Failure::Panic(message) => write!(f, "PANIC  {message}"),
+            Failure::Timeout(timeout) => write!(f, "TIMEOUT  after {timeout:?}"),
+            Failure::OutOfMemory => f.write_str("OOM  ran out of memory"),
+            Failure::Signal(signal) => write!(f, "KILLED  by signal {signal}"),
+        }
+    }
+}
+
+impl error::Error for Failure {}
+
+#[cfg(unix)]
+const SIGKILL: i32 = libc::SIGKILL;
+#[cfg(not(unix))]
+const SIGKILL: i32 = 9;
+
+#[cfg(unix)]
+fn signal(status: ExitStatus) -> Option<i32> {
+    std::os::unix::process::ExitStatusExt::signal(&status)
+}
+
+#[cfg(not(unix))]
+fn signal(_: ExitStatus) -> Option<i32> {
+    None
+}
+
+/// What went wrong with a child that exited with `status`, if anything,
+/// judging by its `stderr`.
+fn classify(status: ExitStatus, stderr: &str) -> Option<Failure> {
+    if status.success() {
+        return None;
+    }
+    if stderr.contains("memory allocation of") {
+        return Some(Failure::OutOfMemory);
+    }
+    if let Some(start) = stderr.find("panicked at ") {
+        let message = stderr[start..]
+            .lines()
+            .take_while(|line| !line.starts_with("note: ") && line.trim() != "stack backtrace:")
+            .collect::<Vec<_>>()
+            .join(" ");
+        return Some(Failure::Panic(message));
+    }
+    match signal(status) {
+        // Nothing here kills with SIGKILL except on a timeout, which is
+        // caught before this, so it's the kernel's OOM killer.
+        Some(SIGKILL) => Some(Failure::OutOfMemory),
+        Some(signal) => Some(Failure::Signal(signal)),
+        None => Some(Failure::Error(
+            stderr
+                .trim()
+                .lines()
+                .map(|line| line.strip_prefix("Error: ").unwrap_or(line))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )),
+    }
+}
+
+#[cfg(unix)]
+fn limit_memory(command: &mut Command, bytes: u64) {
+    use std::os::unix::process::CommandExt;
+
+    let limit = libc::rlimit {
+        rlim_cur: bytes,
+        rlim_max: bytes,
+    };
+    // SAFETY: `setrlimit` is async-signal-safe and only touches the child.
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setrlimit(libc::RLIMIT_AS, &raw const limit) == 0 {
+                Ok(())
+            } else {
+                Err(io::Error::last_os_error())
+            }
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn limit_memory(_: &mut Command, _: u64) {}
+
+/// Start the child in a process group of its own, so that a timeout can stop
+/// whatever it starts too, like the program a `sh -c` script runs.
+#[cfg(unix)]
+fn own_group(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+
+    command.process_group(0);
+}
+
+#[cfg(not(unix))]
+fn own_group(_: &mut Command) {}
+
+/// Kill `child` and everything else in its process group.
+#[cfg(unix)]
+fn kill(child: &mut Child) -> io::Result<()> {
+    let group = libc::pid_t::try_from(child.id()).map_err(io::Error::other)?;
+    // SAFETY: `kill` only sends a signal; the child leads the group.
+    if unsafe { libc::kill(-group, libc::SIGKILL) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(unix))]
+fn kill(child: &mut Child) -> io::Result<()> {
+    child.kill()
+}
+
+fn drain(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut out = Vec::new();
+        let _ = pipe.read_to_end(&mut out);
+        out
+    })
+}
+
+/// Wait for `child` until `timeout`, killing it and its process group then;
+/// `true` if it was killed.
+fn wait(child: &mut Child, timeout: Duration) -> Result<(ExitStatus, bool)> {
+    let start = Instant::now();
+    let mut pause = Duration::from_millis(1);
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok((status, false));
+        }
+        if start.elapsed() >= timeout {
+            kill(child)?;
+            return Ok((child.wait()?, true));
+        }
+        thread::sleep(pause);
+        pause = (pause * 2).min(Duration::from_millis(50));
+    }
+}
+
+/// Run `command` within `limits`, with `input` on its stdin, and return its
+/// stdout; a child that fails is an error holding the [`Failure`].
+pub fn run(mut command: Command, input: Option<String>, limits: Limits) -> Result<String> {
+    if let Some(bytes) = limits.memory {
+        limit_memory(&mut command, bytes);
+    }
+    own_group(&mut command);
+    let stdin = if input.is_some() {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    };
+    let mut child = command
+        .stdin(stdin)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        // A child that exits without reading all of it closes the pipe, which
+        // isn't an error here.
+        thread::spawn(move || stdin.write_all(input.as_bytes()));
+    }
+    let stdout = drain(child.stdout.take().expect("stdout is piped"));
+    let stderr = drain(child.stderr.take().expect("stderr is piped"));
+    let (status, killed) = wait(&mut child, limits.timeout)?;
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = String::from_utf8_lossy(&stderr.join().unwrap_or_default()).into_owned();
+    if killed {
+        return Err(Failure::Timeout(limits.timeout).into());
+    }
+    if let Some(failure) = classify(status, &stderr) {
+        return Err(failure.into());
+    }
+    Ok(String::from_utf8(stdout)?)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::process::ExitStatusExt;
+
+    use super::*;
+
+    const LIMITS: Limits = Limits {
+        timeout: Duration::from_secs(10),
+        memory: None,
+    };
+
+    fn sh(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+
+    fn failure(script: &str, limits: Limits) -> Failure {
+        let err = run(sh(script), None, limits).unwrap_err();
+        err.downcast_ref::<Failure>().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_classify() {
+        let exited = |code| ExitStatus::from_raw(code << 8);
+        assert_eq!(classify(exited(0), "ignored"), None);
+        assert_eq!(
+            classify(
+                exited(101),
+                "\nthread 'main' panicked at src/lib.rs:80:17:\nindex out of bounds\nstack backtrace:\n   0: main\n"
+            ),
+            Some(Failure::Panic(
+                "panicked at src/lib.rs:80:17: index out of bounds".into()
+            ))
+        );
+        assert_eq!(
+            classify(
+                ExitStatus::from_raw(libc::SIGABRT),
+                "memory allocation of 1024 bytes failed\n"
+            ),
+            Some(Failure::OutOfMemory)
+        );
+        assert_eq!(
+            classify(ExitStatus::from_raw(libc::SIGKILL), ""),
+            Some(Failure::OutOfMemory)
+        );
+        assert_eq!(
+            classify(ExitStatus::from_raw(libc::SIGSEGV), ""),
+            Some(Failure::Signal(libc::SIGSEGV))
+        );
+        assert_eq!(
+            classify(exited(1), "Error: no such stack\n"),
+            Some(Failure::Error("no such stack".into()))
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        assert_eq!(
+            run(sh("echo answer 1 7"), None, LIMITS).unwrap(),
+            "answer 1 7\n"
+        );
+        assert_eq!(
+            failure("echo 'Error: bad input' >&2; exit 1", LIMITS),
+            Failure::Error("bad input".into())
+        );
+        assert_eq!(
+            run(sh("wc -l"), Some("a\nb\n".into()), LIMITS)
+                .unwrap()
+                .trim(),
+            "2"
+        );
+    }
+
+    #[test]
+    fn test_timeout() {
+        let limits = Limits {
+            timeout: Duration::from_millis(100),
+            ..LIMITS
+        };
+        let start = Instant::now();
+        assert_eq!(
+            failure("exec sleep 10", limits),
+            Failure::Timeout(limits.timeout)
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        // The shell forks `sleep`, which holds stdout open after the shell
+        // itself is killed.
+        let start = Instant::now();
+        assert_eq!(
+            failure("sleep 10; true", limits),
+            Failure::Timeout(limits.timeout)
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        let limits = Limits {
+            memory: Some(64 << 20),
+            ..LIMITS
+        };
+        // `ulimit -v` shows the child's address space limit in KiB.
+        let limit = run(sh("ulimit -v"), None, limits).unwrap();
+        assert_eq!(limit.trim(), (64 << 10).to_string());
+    }
+}
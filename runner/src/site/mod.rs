@@ -0,0 +1,408 @@
+//! A static write-up site with a page per day: the puzzle text from
+//! `puzzle.md`, the solution's source highlighted, whether each part's
+//! answer is verified, and the latest timings. Every page is self-contained
+//! HTML with its styles inline, so the site works offline.
+use std::{fmt::Write, fs, path::Path};
+
+use aoc::{solution::Stage, store::Puzzle, Result};
+use pulldown_cmark::{html, Parser};
+
+use crate::perf::{format_nanos, stage_label};
+
+/// Everything that goes on a day's page.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Day {
+    pub puzzle: Puzzle,
+    /// The puzzle text, as Markdown.
+    pub text: Option<String>,
+    /// The solution's `lib.rs`.
+    pub source: Option<String>,
+    /// Whether each part's answer is in the store.
+    pub verified: [bool; 2],
+    /// The latest and fastest recorded time of each stage that has one.
+    pub timings: Vec<(Stage, u128, u128)>,
+}
+
+impl Day {
+    /// The title from the first line of the puzzle text, like `Day 5: Supply
+    /// Stacks`, whether it's a heading as `aoc puzzle` writes it or the
+    /// older `\--- Day 5: ... ---` style, or else just the day.
+    fn title(&self) -> String {
+        self.text
+            .as_deref()
+            .and_then(|text| text.lines().next())
+            .map(|line| {
+                line.trim_matches(|c: char| matches!(c, '\\' | '-' | '#') || c.is_whitespace())
+            })
+            .filter(|title| !title.is_empty())
+            .map_or_else(|| format!("Day {}", self.puzzle.day), str::to_string)
+    }
+
+    fn file_name(&self) -> String {
+        format!("{}.html", self.puzzle.package())
+    }
+}
+
+const STYLE: &str = "\
+body { font-family: sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; \
+line-height: 1.5; color: #222; }
+pre { background: #f6f8fa; padding: 1rem; overflow-x: auto; line-height: 1.35; }
+code { font-family: monospace; }
+table { border-collapse: collapse; }
+th, td { padding: 0.25rem 0.75rem; border-bottom: 1px solid #ddd; text-align: left; }
+td.time { text-align: right; font-family: monospace; }
+em { font-style: normal; font-weight: bold; }
+.k { color: #a626a4; } .s { color: #50a14f; } .c { color: #a0a1a7; font-style: italic; }
+.n { color: #986801; } .m { color: #4078f2; } .t { color: #c18401; } .a { color: #e45649; }
+";
+
+/// `text` with the characters that mean something in HTML escaped.
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// The puzzle text as HTML, without its title line, which the page shows
+/// as its heading instead.
+pub fn markdown(text: &str) -> String {
+    let body = match text.split_once('\n') {
+        Some((first, rest)) if first.starts_with("# ") || first.starts_with("\\---") => rest,
+        _ => text,
+    };
+    let body = body.trim_start_matches(|c: char| c == '-' || c.is_whitespace());
+    let mut out = String::new();
+    html::push_html(&mut out, Parser::new(body));
+    out
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while",
+];
+
+/// Wrap `text` in a span of `class`, escaped.
+fn span(out: &mut String, class: &str, text: &str) {
+    let _ = write!(out, "<span class=\"{class}\">{}</span>", escape(text));
+}
+
+/// The length of the string or char literal at the start of `rest`, which
+/// starts with `"`, `'` or a raw string's `r`, or `None` if a `'` starts a
+/// lifetime.
+fn literal_len(rest: &str) -> Option<usize> {
+    if let Some(raw) = rest.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let open = raw[hashes..].strip_prefix('"')?;
+        let close = format!("\"{}", "#".repeat(hashes));
+        let end = open
+            .find(&close)
+            .map_or(open.len(), |end| end + close.len());
+        return Some(1 + hashes + 1 + end);
+    }
+    let quote = rest.chars().next()?;
+    let mut chars = rest.char_indices().skip(1);
+    if quote == '\'' {
+        // A char literal is one character or escape, then the quote.
+        let (_, c) = chars.next()?;
+        if c != '\\' && !rest[1 + c.len_utf8()..].starts_with('\'') {
+            return None;
+        }
+    }
+    let mut escaped = false;
+    for (idx, c) in rest.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            c if c == quote => return Some(idx + 1),
+            _ => {}
+        }
+    }
+    Some(rest.len())
+}
+
+/// Whether `rest` starts with a raw string, `r` then any number of `#` and
+/// a `"`.
+fn raw_string(rest: &str) -> bool {
+    rest.strip_prefix('r')
+        .is_some_and(|raw| raw.trim_start_matches('#').starts_with('"'))
+}
+
+/// Rust source as HTML, with keywords, literals, comments, macros, types
+/// and attributes in spans that the site's style colours.
+pub fn highlight(source: &str) -> String {
+    let mut out = String::with_capacity(source.len() * 2);
+    let mut rest = source;
+    while let Some(c) = rest.chars().next() {
+        let len = if rest.starts_with("//") {
+            let len = rest.find('\n').unwrap_or(rest.len());
+            span(&mut out, "c", &rest[..len]);
+            len
+        } else if rest.starts_with("/*") {
+            let len = rest.find("*/").map_or(rest.len(), |end| end + 2);
+            span(&mut out, "c", &rest[..len]);
+            len
+        } else if rest.starts_with("#[") || rest.starts_with("#![") {
+            let len = rest.find(']').map_or(rest.len(), |end| end + 1);
+            span(&mut out, "a", &rest[..len]);
+            len
+        } else if let Some(len) = (c == '"' || c == '\'' || raw_string(rest))
+            .then(|| literal_len(rest))
+            .flatten()
+        {
+            span(&mut out, "s", &rest[..len]);
+            len
+        } else if c.is_ascii_digit() {
+            // A `.` is only part of the number before a digit, so this stops
+            // before a range like `1..2` or a method call like `1.max(2)`.
+            let mut chars = rest.char_indices().peekable();
+            let len = loop {
+                match chars.next() {
+                    Some((_, c)) if c.is_ascii_alphanumeric() || c == '_' => {}
+                    Some((_, '.'))
+                        if chars.peek().is_some_and(|&(_, next)| next.is_ascii_digit()) => {}
+                    Some((idx, _)) => break idx,
+                    None => break rest.len(),
+                }
+            };
+            span(&mut out, "n", &rest[..len]);
+            len
+        } else if c.is_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..len];
+            if rest[len..].starts_with('!') && !rest[len..].starts_with("!=") {
+                span(&mut out, "m", &rest[..=len]);
+                len + 1
+            } else {
+                if KEYWORDS.contains(&word) {
+                    span(&mut out, "k", word);
+                } else if word.starts_with(char::is_uppercase) {
+                    span(&mut out, "t", word);
+                } else {
+                    out.push_str(word);
+                }
+                len
+            }
+        } else {
+            out.push_str(&escape(&rest[..c.len_utf8()]));
+            c.len_utf8()
+        };
+        rest = &rest[len..];
+    }
+    out
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<style>\n{STYLE}</style>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape(title)
+    )
+}
+
+fn verified_label(verified: bool) -> &'static str {
+    if verified {
+        "✓ verified"
+    } else {
+        "not verified"
+    }
+}
+
+/// The page for one day.
+pub fn render_day(day: &Day) -> String {
+    let puzzle = day.puzzle;
+    let title = day.title();
+    let mut body = format!(
+        "<p><a href=\"index.html\">{}</a></p>\n<h1>{}</h1>\n",
+        puzzle.year,
+        escape(&title)
+    );
+
+    body.push_str("<h2>Results</h2>\n<table>\n<tr><th>Stage</th><th>Answer</th><th>Latest</th><th>Fastest</th></tr>\n");
+    for stage in [Stage::Parse, Stage::Part1, Stage::Part2] {
+        let answer = match stage {
+            Stage::Parse => "",
+            Stage::Part1 => verified_label(day.verified[0]),
+            Stage::Part2 => verified_label(day.verified[1]),
+        };
+        let (latest, fastest) = day.timings.iter().find(|(s, _, _)| *s == stage).map_or(
+            ("-".to_string(), "-".to_string()),
+            |&(_, latest, fastest)| (format_nanos(latest), format_nanos(fastest)),
+        );
+        let _ = writeln!(
+            body,
+            "<tr><td>{}</td><td>{answer}</td><td class=\"time\">{latest}</td><td class=\"time\">{fastest}</td></tr>",
+            stage_label(stage)
+        );
+    }
+    body.push_str("</table>\n");
+
+    body.push_str("<h2>Puzzle</h2>\n");
+    match &day.text {
+        Some(text) => body.push_str(&markdown(text)),
+        None => body.push_str("<p>No <code>puzzle.md</code> yet.</p>\n"),
+    }
+
+    if let Some(source) = &day.source {
+        let _ = write!(
+            body,
+            "<h2>Solution</h2>\n<p><code>{}/src/lib.rs</code></p>\n<pre><code>{}</code></pre>\n",
+            puzzle.member(),
+            highlight(source)
+        );
+    }
+    page(&format!("{title} ({})", puzzle.year), &body)
+}
+
+/// The index page, linking to each day.
+pub fn render_index(days: &[Day]) -> String {
+    let mut body = String::from("<h1>Advent of Code write-ups</h1>\n");
+    let mut year = None;
+    for day in days {
+        let puzzle = day.puzzle;
+        if year != Some(puzzle.year) {
+            if year.is_some() {
+                body.push_str("</ul>\n");
+            }
+            let _ = writeln!(body, "<h2>{}</h2>\n<ul>", puzzle.year);
+            year = Some(puzzle.year);
+        }
+        let stars = "★".repeat(day.verified.iter().filter(|&&verified| verified).count());
+        let _ = writeln!(
+            body,
+            "<li><a href=\"{}\">{}</a> {stars}</li>",
+            day.file_name(),
+            escape(&day.title())
+        );
+    }
+    if year.is_some() {
+        body.push_str("</ul>\n");
+    }
+    page("Advent of Code write-ups", &body)
+}
+
+/// Write the index and a page for each of `days` into `out`.
+pub fn write(out: &Path, days: &[Day]) -> Result<()> {
+    fs::create_dir_all(out)?;
+    fs::write(out.join("index.html"), render_index(days))?;
+    for day in days {
+        fs::write(out.join(day.file_name()), render_day(day))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day() -> Day {
+        Day {
+            puzzle: Puzzle::new(2022, 5).unwrap(),
+            text: Some(
+                "\\--- Day 5: Supply Stacks ---\n----------\n\nMove *crates* & more.\n\n```\n[D]\n```\n"
+                    .into(),
+            ),
+            source: Some("fn part1() -> u32 {\n    todo!()\n}\n".into()),
+            verified: [true, false],
+            timings: vec![(Stage::Part1, 1_500, 1_000)],
+        }
+    }
+
+    #[test]
+    fn test_title() {
+        assert_eq!(day().title(), "Day 5: Supply Stacks");
+        let untitled = Day {
+            text: None,
+            ..day()
+        };
+        assert_eq!(untitled.title(), "Day 5");
+        let heading = Day {
+            text: Some("# Day 5: Supply Stacks\n\nMove crates.\n".into()),
+            ..day()
+        };
+        assert_eq!(heading.title(), "Day 5: Supply Stacks");
+        assert_eq!(
+            markdown(heading.text.as_deref().unwrap()),
+            "<p>Move crates.</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown() {
+        let html = markdown(day().text.as_deref().unwrap());
+        assert!(!html.contains("Day 5"));
+        assert!(html.starts_with("<p>Move <em>crates</em>"));
+        assert!(html.contains("<pre><code>[D]\n</code></pre>"));
+    }
+
+    #[test]
+    fn test_highlight() {
+        assert_eq!(
+            highlight("let x = \"<a>\"; // done"),
+            "<span class=\"k\">let</span> x = <span class=\"s\">&quot;&lt;a&gt;&quot;</span>; \
+             <span class=\"c\">// done</span>"
+        );
+        assert_eq!(
+            highlight("fn f<'a>(c: char) -> Vec<&'a str> { vec!['x', '\\''] }"),
+            "<span class=\"k\">fn</span> f&lt;'a&gt;(c: char) -&gt; <span class=\"t\">Vec</span>&lt;&amp;'a str&gt; { \
+             <span class=\"m\">vec!</span>[<span class=\"s\">'x'</span>, <span class=\"s\">'\\''</span>] }"
+        );
+        assert_eq!(
+            highlight("#[test]\nr#\"a\"b\"#; 1_000u32..2 x != y"),
+            "<span class=\"a\">#[test]</span>\n<span class=\"s\">r#&quot;a&quot;b&quot;#</span>; \
+             <span class=\"n\">1_000u32</span>..<span class=\"n\">2</span> x != y"
+        );
+        assert_eq!(
+            highlight("r##\"a\"#b\"##, r\"c\", raw"),
+            "<span class=\"s\">r##&quot;a&quot;#b&quot;##</span>, \
+             <span class=\"s\">r&quot;c&quot;</span>, raw"
+        );
+        assert_eq!(
+            highlight("1.max(2) + 1.5e3"),
+            "<span class=\"n\">1</span>.max(<span class=\"n\">2</span>) + <span class=\"n\">1.5e3</span>"
+        );
+    }
+
+    #[test]
+    fn test_render_day() {
+        let html = render_day(&day());
+        assert!(html.contains("<title>Day 5: Supply Stacks (2022)</title>"));
+        assert!(html.contains("<td>part 1</td><td>✓ verified</td><td class=\"time\">1.5µs</td><td class=\"time\">1.0µs</td>"));
+        assert!(html.contains("<td>part 2</td><td>not verified</td><td class=\"time\">-</td>"));
+        assert!(html.contains("Move <em>crates</em> &amp; more."));
+        assert!(html.contains("<span class=\"m\">todo!</span>()"));
+        // Nothing is fetched from elsewhere.
+        assert!(!html.contains("http"));
+    }
+
+    #[test]
+    fn test_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let other = Day {
+            puzzle: Puzzle::new(2023, 1).unwrap(),
+            text: None,
+            source: None,
+            verified: [false, false],
+            timings: Vec::new(),
+        };
+        write(dir.path(), &[day(), other]).unwrap();
+        let index = fs::read_to_string(dir.path().join("index.html")).unwrap();
+        assert!(index.contains("<a href=\"y2022-d05.html\">Day 5: Supply Stacks</a> ★</li>"));
+        assert!(index.contains("<h2>2023</h2>"));
+        assert!(dir.path().join("y2023-d01.html").exists());
+        let page = fs::read_to_string(dir.path().join("y2023-d01.html")).unwrap();
+        assert!(page.contains("No <code>puzzle.md</code> yet."));
+    }
+}
@@ -0,0 +1,308 @@
+//! How far along each day of a year is: whether it has been scaffolded,
+//! which parts are solved, whether its tests pass and how long it last took,
+//! as a calendar for the terminal or a table for the README.
+use std::{collections::BTreeMap, fmt::Write};
+
+use aoc::{solution::Stage, store::Puzzle};
+
+use crate::{history::Entry, perf::format_nanos};
+
+/// How far a day has got, in order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Progress {
+    NotStarted,
+    Scaffolded,
+    Part1,
+    Part2,
+}
+
+impl Progress {
+    /// What a day has got to with source `lib` (if its directory exists) and
+    /// a stored answer for each part in `stored`. A part only counts as
+    /// solved if it has an answer and no `todo!()` left.
+    pub fn of(lib: Option<&str>, stored: [bool; 2]) -> Self {
+        let Some(lib) = lib else {
+            return Progress::NotStarted;
+        };
+        let solved = |part, stored| stored && !unfinished(lib, part);
+        match (
+            solved(Stage::Part1, stored[0]),
+            solved(Stage::Part2, stored[1]),
+        ) {
+            (true, true) => Progress::Part2,
+            (true, false) => Progress::Part1,
+            (false, _) => Progress::Scaffolded,
+        }
+    }
+
+    fn mark(self) -> &'static str {
+        match self {
+            Progress::NotStarted => "",
+            Progress::Scaffolded => "o",
+            Progress::Part1 => "*",
+            Progress::Part2 => "**",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Progress::NotStarted => "not started",
+            Progress::Scaffolded => "scaffolded",
+            Progress::Part1 => "part 1 solved",
+            Progress::Part2 => "part 2 solved",
+        }
+    }
+}
+
+/// Whether the function for `part` in a day's `lib.rs` is still a stub, as
+/// `create.sh` leaves it.
+pub fn unfinished(lib: &str, part: Stage) -> bool {
+    let name = match part {
+        Stage::Parse => "fn parse_input(",
+        Stage::Part1 => "fn part1(",
+        Stage::Part2 => "fn part2(",
+    };
+    let Some(start) = lib.find(name) else {
+        return false;
+    };
+    // Top-level functions end at the first closing brace in column one.
+    let body = &lib[start..];
+    let body = body.find("\n}").map_or(body, |end| &body[..end]);
+    body.contains("todo!(") || body.contains("unimplemented!(")
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayStatus {
+    pub puzzle: Puzzle,
+    pub progress: Progress,
+    /// Whether the day is a member of the workspace, rather than commented
+    /// out of it.
+    pub member: bool,
+    /// Whether its tests passed, if they were run.
+    pub tests: Option<bool>,
+    /// How long its latest recorded run took, parsing included.
+    pub nanos: Option<u128>,
+}
+
+impl DayStatus {
+    fn tests_label(&self) -> &'static str {
+        match self.tests {
+            Some(true) => "passing",
+            Some(false) => "failing",
+            None => "-",
+        }
+    }
+
+    fn time_label(&self) -> String {
+        self.nanos.map_or_else(|| "-".to_string(), format_nanos)
+    }
+}
+
+/// The total time of the latest recorded run of each puzzle in `entries`.
+pub fn latest_times(entries: &[Entry]) -> BTreeMap<Puzzle, u128> {
+    let mut latest: BTreeMap<(Puzzle, Stage), &Entry> = BTreeMap::new();
+    for entry in entries {
+        let slot = latest.entry((entry.puzzle, entry.stage)).or_insert(entry);
+        if entry.timestamp >= slot.timestamp {
+            *slot = entry;
+        }
+    }
+    let mut totals = BTreeMap::new();
+    for ((puzzle, _), entry) in latest {
+        *totals.entry(puzzle).or_default() += entry.nanos;
+    }
+    totals
+}
+
+/// The day of the week of 1 December in `year`, from 0 for Monday.
+fn first_weekday(year: u16) -> usize {
+    // Sakamoto's method, which counts from 0 for Sunday.
+    let year = usize::from(year);
+    (year + year / 4 - year / 100 + year / 400 + 4 + 1 + 6) % 7
+}
+
+/// A December calendar with each day's progress next to its date, then a
+/// line for each day that has been started.
+pub fn render_calendar(year: u16, days: &[DayStatus]) -> String {
+    let mut out = format!("December {year}\n");
+    out.push_str(" Mo   Tu   We   Th   Fr   Sa   Su\n");
+    let mut line = "     ".repeat(first_weekday(year));
+    for day in 1..=25 {
+        let mark = days
+            .iter()
+            .find(|status| status.puzzle.day == day)
+            .map_or("", |status| status.progress.mark());
+        let _ = write!(line, "{day:>3}{mark:<2}");
+        if line.len() >= 35 || day == 25 {
+            out.push_str(line.trim_end());
+            out.push('\n');
+            line.clear();
+        }
+    }
+    out.push_str("\no scaffolded, * part 1, ** part 2\n");
+
+    let started: Vec<_> = days
+        .iter()
+        .filter(|status| status.progress != Progress::NotStarted)
+        .collect();
+    if !started.is_empty() {
+        out.push('\n');
+    }
+    for status in started {
+        let _ = write!(
+            out,
+            "{}  {:<13}  tests {:<7}  {:>9}",
+            status.puzzle,
+            status.progress.label(),
+            status.tests_label(),
+            status.time_label()
+        );
+        if !status.member {
+            out.push_str("  (not a workspace member)");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// A Markdown table with a row for every day.
+pub fn render_markdown(days: &[DayStatus]) -> String {
+    let mut out = String::from("| Day | Stars | Status | Tests | Time |\n|--:|:-:|---|---|--:|\n");
+    for status in days {
+        let stars = match status.progress {
+            Progress::Part1 => "⭐",
+            Progress::Part2 => "⭐⭐",
+            Progress::NotStarted | Progress::Scaffolded => "",
+        };
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {} |",
+            status.puzzle.day,
+            stars,
+            status.progress.label(),
+            status.tests_label(),
+            status.time_label()
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STUB: &str = "\
+pub fn part1(_input: &[String]) -> Result<u32> {
+    todo!()
+}
+
+pub fn part2(_input: &[String]) -> Result<u32> {
+    todo!()
+}
+";
+
+    fn status(day: u8, progress: Progress) -> DayStatus {
+        DayStatus {
+            puzzle: Puzzle::new(2022, day).unwrap(),
+            progress,
+            member: true,
+            tests: None,
+            nanos: None,
+        }
+    }
+
+    #[test]
+    fn test_unfinished() {
+        assert!(unfinished(STUB, Stage::Part1));
+        assert!(unfinished(STUB, Stage::Part2));
+        let half = STUB.replacen("todo!()", "Ok(1)", 1);
+        assert!(!unfinished(&half, Stage::Part1));
+        assert!(unfinished(&half, Stage::Part2));
+        assert!(!unfinished("fn part1_arena() {}\n", Stage::Part1));
+        assert!(!unfinished("", Stage::Part2));
+    }
+
+    #[test]
+    fn test_progress() {
+        let half = STUB.replacen("todo!()", "Ok(1)", 1);
+        let done = half.replace("todo!()", "Ok(2)");
+        assert_eq!(Progress::of(None, [true, true]), Progress::NotStarted);
+        assert_eq!(Progress::of(Some(STUB), [true, true]), Progress::Scaffolded);
+        assert_eq!(Progress::of(Some(&half), [true, true]), Progress::Part1);
+        assert_eq!(Progress::of(Some(&done), [true, false]), Progress::Part1);
+        assert_eq!(
+            Progress::of(Some(&done), [false, true]),
+            Progress::Scaffolded
+        );
+        assert_eq!(Progress::of(Some(&done), [true, true]), Progress::Part2);
+    }
+
+    #[test]
+    fn test_latest_times() {
+        let puzzle = Puzzle::new(2022, 1).unwrap();
+        let entry = |timestamp, stage, nanos| Entry {
+            timestamp,
+            commit: "abc".into(),
+            machine: "m".into(),
+            puzzle,
+            stage,
+            nanos,
+        };
+        let entries = [
+            entry(1, Stage::Parse, 100),
+            entry(1, Stage::Part1, 1000),
+            entry(2, Stage::Parse, 50),
+            entry(2, Stage::Part1, 500),
+            entry(2, Stage::Part2, 700),
+        ];
+        assert_eq!(latest_times(&entries)[&puzzle], 1250);
+        assert!(latest_times(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_first_weekday() {
+        assert_eq!(first_weekday(2022), 3);
+        assert_eq!(first_weekday(2023), 4);
+        assert_eq!(first_weekday(2024), 6);
+        assert_eq!(first_weekday(2015), 1);
+    }
+
+    #[test]
+    fn test_render_calendar() {
+        let mut days: Vec<_> = (1..=25)
+            .map(|day| status(day, Progress::NotStarted))
+            .collect();
+        days[0] = DayStatus {
+            tests: Some(true),
+            nanos: Some(1_500_000),
+            ..status(1, Progress::Part2)
+        };
+        days[8] = DayStatus {
+            member: false,
+            ..status(9, Progress::Scaffolded)
+        };
+        days[9] = status(10, Progress::Part1);
+        let calendar = render_calendar(2022, &days);
+        let lines: Vec<_> = calendar.lines().collect();
+        assert_eq!(lines[1], " Mo   Tu   We   Th   Fr   Sa   Su");
+        assert_eq!(lines[2], format!("{}1**  2    3    4", " ".repeat(17)));
+        assert_eq!(lines[3], "  5    6    7    8    9o  10*  11");
+        assert_eq!(lines[5], " 19   20   21   22   23   24   25");
+        assert!(calendar.contains("2022 day 01  part 2 solved  tests passing      1.5ms\n"));
+        assert!(calendar.contains(
+            "2022 day 09  scaffolded     tests -                -  (not a workspace member)\n"
+        ));
+        assert!(!calendar.contains("2022 day 02"));
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let days = [status(1, Progress::Part2), status(2, Progress::NotStarted)];
+        assert_eq!(
+            render_markdown(&days),
+            "| Day | Stars | Status | Tests | Time |\n|--:|:-:|---|---|--:|\n\
+             | 1 | ⭐⭐ | part 2 solved | - | - |\n\
+             | 2 |  | not started | - | - |\n"
+        );
+    }
+}
@@ -0,0 +1,72 @@
+//! Show the events a puzzle traced, either as `key=value` lines under each
+//! part or as JSON lines tagged with the puzzle and stage.
+use aoc::{err, solution::Stage, store::Puzzle, Result};
+use serde_json::{json, Value};
+
+fn parse(event: &str) -> Result<(String, serde_json::Map<String, Value>)> {
+    let Value::Object(mut event) = serde_json::from_str(event)? else {
+        return Err(err!("trace event is not an object: {}", event));
+    };
+    let Some(Value::String(name)) = event.remove("event") else {
+        return Err(err!("trace event has no name"));
+    };
+    let fields = match event.remove("fields") {
+        Some(Value::Object(fields)) => fields,
+        None => serde_json::Map::new(),
+        Some(other) => return Err(err!("trace fields are not an object: {}", other)),
+    };
+    Ok((name, fields))
+}
+
+/// An event as `name key=value ...`, with strings quoted.
+pub fn format_event(event: &str) -> Result<String> {
+    let (name, fields) = parse(event)?;
+    Ok(fields
+        .iter()
+        .fold(name, |line, (key, value)| format!("{line} {key}={value}")))
+}
+
+/// An event as a JSON line that says which puzzle and stage it came from.
+pub fn json_line(puzzle: Puzzle, stage: Stage, event: &str) -> Result<String> {
+    let (name, fields) = parse(event)?;
+    Ok(json!({
+        "year": puzzle.year,
+        "day": puzzle.day,
+        "stage": stage.to_string(),
+        "event": name,
+        "fields": fields,
+    })
+    .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENT: &str = r#"{"event":"rucksack","fields":{"line":3,"item":"p","shared":true}}"#;
+
+    #[test]
+    fn test_format_event() {
+        assert_eq!(
+            format_event(EVENT).unwrap(),
+            r#"rucksack line=3 item="p" shared=true"#
+        );
+        assert_eq!(format_event(r#"{"event":"start"}"#).unwrap(), "start");
+    }
+
+    #[test]
+    fn test_json_line() {
+        let puzzle = Puzzle::new(2022, 3).unwrap();
+        assert_eq!(
+            json_line(puzzle, Stage::Part1, EVENT).unwrap(),
+            r#"{"year":2022,"day":3,"stage":"1","event":"rucksack","fields":{"line":3,"item":"p","shared":true}}"#
+        );
+    }
+
+    #[test]
+    fn test_bad_events() {
+        for event in ["", "[]", r#"{"fields":{}}"#, r#"{"event":"a","fields":[]}"#] {
+            assert!(format_event(event).is_err(), "{event}");
+        }
+    }
+}
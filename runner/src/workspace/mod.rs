@@ -0,0 +1,238 @@
+//! Find, build, and run the puzzle crates of this workspace.
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use aoc::{
+    err,
+    solution::{Record, Report, Stage},
+    store::Puzzle,
+    Result,
+};
+
+use crate::sandbox::{self, Limits};
+
+pub struct Workspace {
+    root: PathBuf,
+}
+
+impl Workspace {
+    /// The workspace this runner was built from.
+    pub fn locate() -> Self {
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        Self {
+            root: manifest_dir.parent().unwrap_or(manifest_dir).to_path_buf(),
+        }
+    }
+
+    /// Where the runner keeps its own files, such as the timing history.
+    pub fn state_dir(&self) -> PathBuf {
+        self.root.join(".aoc")
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The solvers in other languages to compare with; see
+    /// [`external`](crate::external).
+    pub fn solvers_path(&self) -> PathBuf {
+        self.root.join("solvers.toml")
+    }
+
+    /// The store of known answers, which is checked in.
+    pub fn answers_path(&self) -> PathBuf {
+        self.root.join("answers.tsv")
+    }
+
+    /// The puzzles that are members of the workspace, in order.
+    pub fn puzzles(&self) -> Result<Vec<Puzzle>> {
+        let manifest = fs::read_to_string(self.root.join("Cargo.toml"))?;
+        Ok(member_puzzles(&manifest))
+    }
+
+    /// Build the release binaries for `puzzles`, with `aoc`'s `trace`
+    /// feature if `trace` is set.
+    pub fn build(&self, puzzles: &[Puzzle], trace: bool) -> Result<()> {
+        let mut cargo = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
+        cargo
+            .current_dir(&self.root)
+            .args(["build", "--release", "--quiet"]);
+        if trace {
+            cargo.args(["--features", "aoc/trace"]);
+        }
+        for puzzle in puzzles {
+            cargo.args(["--package", &puzzle.package()]);
+        }
+        let status = cargo.status()?;
+        if !status.success() {
+            return Err(err!("cargo build failed: {}", status));
+        }
+        Ok(())
+    }
+
+    /// Whether `cargo test` passes for `puzzle`, keeping its output quiet.
+    pub fn test(&self, puzzle: Puzzle) -> Result<bool> {
+        let status = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()))
+            .current_dir(&self.root)
+            .args(["test", "--quiet", "--package", &puzzle.package()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()?;
+        Ok(status.success())
+    }
+
+    fn binary(&self, puzzle: Puzzle) -> PathBuf {
+        let target = std::env::var_os("CARGO_TARGET_DIR")
+            .map_or_else(|| self.root.join("target"), PathBuf::from);
+        target.join("release").join(puzzle.package())
+    }
+
+    /// Run a puzzle's release binary for one `part` within `limits`, and
+    /// collect the reports for parsing and that part, including the events
+    /// they trace if `trace` is set. A run that fails is an error holding a
+    /// [`Failure`](crate::sandbox::Failure).
+    pub fn run(
+        &self,
+        puzzle: Puzzle,
+        part: Stage,
+        trace: bool,
+        params: &[String],
+        limits: Limits,
+    ) -> Result<Vec<Report>> {
+        let mut command = Command::new(self.binary(puzzle));
+        command.current_dir(self.root.join(puzzle.member())).args([
+            "--protocol",
+            "--part",
+            &part.to_string(),
+        ]);
+        if trace {
+            command.arg("--trace");
+        }
+        for param in params {
+            command.args(["--param", param]);
+        }
+        let records = sandbox::run(command, None, limits)?
+            .lines()
+            .map(str::parse)
+            .collect::<Result<Vec<Record>>>()?;
+        collect_reports(&records, &[Stage::Parse, part])
+    }
+
+    /// `git describe` for the workspace, marked `-dirty` if there are
+    /// uncommitted changes.
+    pub fn commit(&self) -> String {
+        Command::new("git")
+            .current_dir(&self.root)
+            .args(["describe", "--always", "--dirty"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map_or_else(
+                || "unknown".to_string(),
+                |output| String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            )
+    }
+}
+
+/// The puzzles listed (and not commented out) in the workspace `members`.
+fn member_puzzles(manifest: &str) -> Vec<Puzzle> {
+    let mut puzzles: Vec<_> = manifest
+        .lines()
+        .filter_map(|line| {
+            let member = line.trim().trim_end_matches(',');
+            Puzzle::from_member(member.strip_prefix('"')?.strip_suffix('"')?)
+        })
+        .collect();
+    puzzles.sort_unstable();
+    puzzles
+}
+
+/// Pair up the answer, time and trace records of each of `stages`.
+fn collect_reports(records: &[Record], stages: &[Stage]) -> Result<Vec<Report>> {
+    let mut answers = BTreeMap::new();
+    let mut times = BTreeMap::new();
+    let mut traces: BTreeMap<Stage, Vec<String>> = BTreeMap::new();
+    for record in records {
+        match record {
+            Record::Answer { stage, answer } => {
+                answers.insert(*stage, answer.clone());
+            }
+            Record::Time { stage, nanos } => {
+                times.insert(*stage, *nanos);
+            }
+            Record::Trace { stage, event } => {
+                traces.entry(*stage).or_default().push(event.clone());
+            }
+        }
+    }
+    stages
+        .iter()
+        .map(|&stage| {
+            let nanos = *times
+                .get(&stage)
+                .ok_or_else(|| err!("no time reported for stage {}", stage))?;
+            Ok(Report {
+                stage,
+                answer: answers.remove(&stage),
+                elapsed: std::time::Duration::from_nanos(u64::try_from(nanos)?),
+                trace: traces.remove(&stage).unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_member_puzzles() {
+        let manifest = r#"
+[workspace]
+members = [
+    "aoc",
+    "runner",
+    "y2022/d02",
+    "y2022/d01",
+    "y2021/d25",
+    # "y2022/d03",
+]
+"#;
+        let puzzles: Vec<_> = member_puzzles(manifest)
+            .into_iter()
+            .map(|p| (p.year, p.day))
+            .collect();
+        assert_eq!(puzzles, vec![(2021, 25), (2022, 1), (2022, 2)]);
+    }
+
+    #[test]
+    fn test_collect_reports() {
+        let records: Vec<Record> = [
+            "time parse 5",
+            "answer 1 CMZ",
+            "time 1 7",
+            r#"trace 1 {"event":"move","fields":{"n":1}}"#,
+            r#"trace 1 {"event":"move","fields":{"n":2}}"#,
+            "answer 2 MCD",
+            "time 2 9",
+        ]
+        .into_iter()
+        .map(|line| line.parse().unwrap())
+        .collect();
+        let reports = collect_reports(&records, &Stage::ALL).unwrap();
+        assert_eq!(reports.len(), 3);
+        assert_eq!(reports[0].answer, None);
+        assert_eq!(reports[2].answer.as_deref(), Some("MCD"));
+        assert_eq!(reports[1].elapsed.as_nanos(), 7);
+        assert_eq!(reports[1].trace.len(), 2);
+        assert!(reports[2].trace.is_empty());
+
+        assert!(collect_reports(&records[..5], &Stage::ALL).is_err());
+        let reports = collect_reports(&records[..5], &[Stage::Parse, Stage::Part1]).unwrap();
+        assert_eq!(reports.len(), 2);
+    }
+}
@@ -0,0 +1,103 @@
+#![warn(clippy::pedantic)]
+#![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+use aoc::{err, iter::AocIterExt, math::checked_sum, solution::Solution, Result};
+use std::collections::HashMap;
+
+pub type ElfMap = HashMap<usize, u32>;
+
+pub static INPUT: &str = include_str!("../input.txt");
+
+aoc::params! {
+    pub struct Elves {
+        /// How many of the best-stocked elves part 2 adds up.
+        top: usize = 3,
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<ElfMap> {
+    input
+        .split("\n\n")
+        .enumerate()
+        .map(|(idx, elem)| {
+            let calories = elem
+                .lines()
+                .map(str::parse::<u32>)
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            Ok((idx, checked_sum(calories)?))
+        })
+        .collect()
+}
+
+pub fn part1(parsed: &ElfMap) -> Result<u32> {
+    parsed
+        .values()
+        .copied()
+        .max()
+        .ok_or_else(|| err!("No max found"))
+}
+
+pub fn part2(parsed: &ElfMap, elves: &Elves) -> Result<u32> {
+    checked_sum(parsed.values().copied().top_k(elves.top))
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 1;
+    type Parsed = ElfMap;
+    type Params = Elves;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<ElfMap> {
+        parse_input(input)
+    }
+
+    fn part1(parsed: &ElfMap, _: &Elves) -> Result<u32> {
+        part1(parsed)
+    }
+
+    fn part2(parsed: &ElfMap, elves: &Elves) -> Result<u32> {
+        part2(parsed, elves)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE_INPUT: &str = "\
+1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    #[test]
+    fn test_parse() {
+        let parsed = parse_input(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(parsed[&0], 6000);
+        assert_eq!(parsed[&1], 4000);
+        assert_eq!(parsed[&2], 11000);
+        assert_eq!(parsed[&3], 24000);
+        assert_eq!(parsed[&4], 10000);
+    }
+
+    aoc::aoc_tests! {
+        Day01;
+        example: EXAMPLE_INPUT => (24000, 45000),
+        top_two: EXAMPLE_INPUT, top = 2 => (24000, 35000),
+        real: INPUT => (stored, stored),
+    }
+}
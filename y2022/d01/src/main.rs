@@ -0,0 +1,3 @@
+fn main() -> aoc::Result<()> {
+    aoc::solution::run::<y2022_d01::Day01>(y2022_d01::INPUT)
+}
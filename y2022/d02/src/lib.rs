@@ -1,12 +1,13 @@
 #![warn(clippy::pedantic)]
-use aoc::{err, parse_input, Error, Result};
+#![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+use aoc::{err, parse_input, solution::Solution, Error, Result};
 use std::result;
 use std::str::FromStr;
 
-const INPUT: &str = include_str!("../input.txt");
+pub const INPUT: &str = include_str!("../input.txt");
 
-#[derive(Clone, PartialEq)]
-enum Move {
+#[derive(Clone, Debug, PartialEq)]
+pub enum Move {
     Rock,
     Paper,
     Scissors,
@@ -23,8 +24,8 @@ impl Move {
     }
 }
 
-#[derive(PartialEq)]
-enum Outcome {
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
     Win,
     Loss,
     Tie,
@@ -40,7 +41,7 @@ impl Outcome {
     }
 }
 
-struct Game(Move, Move);
+pub struct Game(pub Move, pub Move);
 
 impl Game {
     fn score(&self) -> u32 {
@@ -89,16 +90,31 @@ impl FromStr for Move {
     }
 }
 
-// 13675
-fn part1(parsed: &[Game]) -> u32 {
-    parsed.iter().map(Game::score).sum()
+#[must_use]
+pub fn part1(parsed: &[Game]) -> u32 {
+    parsed
+        .iter()
+        .enumerate()
+        .map(|(idx, game)| {
+            let score = game.score();
+            aoc::trace!(
+                "game",
+                round = idx + 1,
+                opponent = ?game.0,
+                me = ?game.1,
+                outcome = ?game.outcome(),
+                score = score,
+            );
+            score
+        })
+        .sum()
 }
 
-// 14184
-fn part2(parsed: &[Game]) -> Result<u32> {
+pub fn part2(parsed: &[Game]) -> Result<u32> {
     parsed
         .iter()
-        .map(|game| {
+        .enumerate()
+        .map(|(idx, game)| {
             use Move::{Paper, Rock, Scissors};
             use Outcome::{Loss, Tie, Win};
             let outcome = match game.1 {
@@ -109,18 +125,45 @@ fn part2(parsed: &[Game]) -> Result<u32> {
 
             let Some(r#move) = [Rock, Paper, Scissors].into_iter().find(|r#move| {
                 let game = Game(game.0.clone(), r#move.clone());
-               game.outcome() == outcome
-            }) else { return Err(err!("no suitable move found"))};
-            Ok(Game(game.0.clone(), r#move).score())
+                game.outcome() == outcome
+            }) else {
+                return Err(err!("no suitable move found"));
+            };
+            let score = Game(game.0.clone(), r#move.clone()).score();
+            aoc::trace!(
+                "game",
+                round = idx + 1,
+                opponent = ?game.0,
+                outcome = ?outcome,
+                me = ?r#move,
+                score = score,
+            );
+            Ok(score)
         })
         .sum()
 }
 
-fn main() -> Result<()> {
-    let parsed = parse_input!(INPUT, Game)?;
-    println!("day 02 part 1: {}", part1(&parsed));
-    println!("day 02 part 2: {}", part2(&parsed)?);
-    Ok(())
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 2;
+    type Parsed = Vec<Game>;
+    type Params = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Game>> {
+        Ok(parse_input!(input, Game)?)
+    }
+
+    fn part1(parsed: &Vec<Game>, (): &()) -> Result<u32> {
+        Ok(part1(parsed))
+    }
+
+    fn part2(parsed: &Vec<Game>, (): &()) -> Result<u32> {
+        part2(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -133,15 +176,9 @@ B X
 C Z
 ";
 
-    #[test]
-    fn test_part1() {
-        let parsed: Vec<Game> = parse_input!(EXAMPLE_INPUT, Game).unwrap();
-        assert_eq!(part1(&parsed), 15);
-    }
-
-    #[test]
-    fn test_part2() {
-        let parsed: Vec<Game> = parse_input!(EXAMPLE_INPUT, Game).unwrap();
-        assert_eq!(part2(&parsed).unwrap(), 12);
+    aoc::aoc_tests! {
+        Day02;
+        example: EXAMPLE_INPUT => (15, 12),
+        real: INPUT => (stored, stored),
     }
 }
@@ -0,0 +1,3 @@
+fn main() -> aoc::Result<()> {
+    aoc::solution::run::<y2022_d02::Day02>(y2022_d02::INPUT)
+}
@@ -0,0 +1,159 @@
+#![warn(clippy::pedantic)]
+#![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+use aoc::{
+    bitset::{Alphabet, BitSet, Letters},
+    err,
+    iter::AocIterExt,
+    solution::Solution,
+    Error, Result,
+};
+
+use std::str::FromStr;
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub type ItemType = char;
+pub type ItemSet = BitSet<Letters>;
+
+#[derive(PartialEq, Debug)]
+pub struct RuckSack(pub ItemSet, pub ItemSet);
+
+/// Items are indexed `a..=z` then `A..=Z`, one less than their priority.
+pub fn priority(item: ItemType) -> Result<u32> {
+    Letters::index(item)
+        .map(|idx| idx + 1)
+        .ok_or_else(|| err!("unscorable character: {}", item))
+}
+
+impl RuckSack {
+    fn in_common(&self) -> impl Iterator<Item = ItemType> {
+        self.0.intersection(&self.1).iter()
+    }
+
+    fn score(&self) -> Result<u32> {
+        self.in_common().map(priority).sum()
+    }
+}
+
+impl FromStr for RuckSack {
+    type Err = Error;
+
+    fn from_str(input: &str) -> std::result::Result<Self, Self::Err> {
+        if input.is_empty() {
+            return Err(err!("empty input"));
+        }
+        let half = input.len() / 2;
+
+        let mut iter = input.chars();
+        Ok(RuckSack(
+            ItemSet::try_from_iter(iter.by_ref().take(half))?,
+            ItemSet::try_from_iter(iter.take(half))?,
+        ))
+    }
+}
+
+pub fn part1(input: &str) -> Result<u32> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            let r: RuckSack = line.parse()?;
+            let score = r.score()?;
+            aoc::trace!(
+                "rucksack",
+                line = idx + 1,
+                shared = r.in_common().collect::<String>(),
+                priority = score,
+            );
+            Ok(score)
+        })
+        .sum()
+}
+
+pub fn part2(input: &str) -> Result<u32> {
+    let itemsets: Vec<ItemSet> = input
+        .lines()
+        .map(|line| ItemSet::try_from_iter(line.chars()))
+        .collect::<Result<_>>()?;
+    itemsets
+        .into_iter()
+        .chunked_intersection(3)
+        .enumerate()
+        .map(|(idx, badge_candidates)| {
+            let len = badge_candidates.len();
+            if len != 1 {
+                return Err(err!("expected 1 badge candidate, found {}", len));
+            }
+            let badge = badge_candidates
+                .first()
+                .ok_or_else(|| err!("no badge candidate"))?;
+            let priority = priority(badge)?;
+            aoc::trace!("group", group = idx + 1, badge = badge, priority = priority);
+            Ok(priority)
+        })
+        .try_sum()
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 3;
+    type Parsed = String;
+    type Params = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &String, (): &()) -> Result<u32> {
+        part1(input)
+    }
+
+    fn part2(input: &String, (): &()) -> Result<u32> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE_INPUT: &str = "\
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+    #[test]
+    fn test_make_rucksack() {
+        let r: RuckSack = EXAMPLE_INPUT.lines().next().unwrap().parse().unwrap();
+        let expected = RuckSack(
+            "vJrwpWtwJgWr".chars().collect(),
+            "hcsFMMfFFhFp".chars().collect(),
+        );
+        assert_eq!(r, expected);
+    }
+
+    #[test]
+    fn test_score_ruckscack() {
+        let r = RuckSack(
+            "vJrwpWtwJgWr".chars().collect(),
+            "hcsFMMfFFhFp".chars().collect(),
+        );
+        let common: ItemSet = r.in_common().collect();
+        assert_eq!(common, ItemSet::try_from_iter(['p']).unwrap());
+        assert_eq!(r.score().unwrap(), 16);
+    }
+
+    aoc::aoc_tests! {
+        Day03;
+        example: EXAMPLE_INPUT => (157, 70),
+        real: INPUT => (stored, stored),
+    }
+}
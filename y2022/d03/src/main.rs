@@ -0,0 +1,3 @@
+fn main() -> aoc::Result<()> {
+    aoc::solution::run::<y2022_d03::Day03>(y2022_d03::INPUT)
+}
@@ -0,0 +1,118 @@
+#![warn(clippy::pedantic)]
+#![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+use aoc::{
+    diagnostic::DiagnosticExt, ranges::Interval, solution::Solution, AocParse, Error, Result,
+};
+
+use std::result;
+use std::str::FromStr;
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+#[derive(PartialEq, Debug)]
+pub struct Section(pub Interval<usize>);
+
+#[derive(AocParse, PartialEq, Debug)]
+#[aoc(pattern = "{0},{1}")]
+pub struct ElfPair(pub Section, pub Section);
+
+impl ElfPair {
+    fn duplicated_effort(&self) -> bool {
+        self.0 .0.contains_interval(&self.1 .0) || self.1 .0.contains_interval(&self.0 .0)
+    }
+
+    fn partial_overlaps(&self) -> bool {
+        self.0 .0.overlaps(&self.1 .0)
+    }
+}
+
+#[must_use]
+pub fn part1(elfpairs: &[ElfPair]) -> usize {
+    elfpairs.iter().filter(|ep| ep.duplicated_effort()).count()
+}
+
+#[must_use]
+pub fn part2(elfpairs: &[ElfPair]) -> usize {
+    elfpairs.iter().filter(|ep| ep.partial_overlaps()).count()
+}
+
+impl FromStr for Section {
+    type Err = Error;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 4;
+    type Parsed = Vec<ElfPair>;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<ElfPair>> {
+        input
+            .lines()
+            .map(|line| line.parse().at(input, line))
+            .collect()
+    }
+
+    fn part1(pairs: &Vec<ElfPair>, (): &()) -> Result<usize> {
+        Ok(part1(pairs))
+    }
+
+    fn part2(pairs: &Vec<ElfPair>, (): &()) -> Result<usize> {
+        Ok(part2(pairs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::diagnostic::Diagnostic;
+
+    static EXAMPLE_INPUT: &str = "\
+2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+
+    #[test]
+    fn test_parse_section() {
+        let section: Section = "2-4".parse().unwrap();
+        assert_eq!(section, Section(Interval::new(2, 4).unwrap()));
+    }
+
+    #[test]
+    fn test_parse_elfpair() {
+        let elfpair: ElfPair = EXAMPLE_INPUT.lines().next().unwrap().parse().unwrap();
+        let expected = ElfPair(
+            Section(Interval::new(2, 4).unwrap()),
+            Section(Interval::new(6, 8).unwrap()),
+        );
+        assert_eq!(elfpair, expected);
+    }
+
+    #[test]
+    fn test_parse_error_points_at_field() {
+        let err = Day04::parse("2-4,6-8\n5-7,6-x\n").unwrap_err();
+        let diagnostic = err.downcast_ref::<Diagnostic>().unwrap();
+        assert_eq!(
+            (diagnostic.line, diagnostic.column, diagnostic.width),
+            (2, 5, 3)
+        );
+    }
+
+    aoc::aoc_tests! {
+        Day04;
+        example: EXAMPLE_INPUT => (2, 4),
+        real: INPUT => (stored, stored),
+    }
+}
@@ -0,0 +1,3 @@
+fn main() -> aoc::Result<()> {
+    aoc::solution::run::<y2022_d04::Day04>(y2022_d04::INPUT)
+}
@@ -0,0 +1,201 @@
+#![warn(clippy::pedantic)]
+#![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+use std::{result, str::FromStr};
+
+use aoc::{diagnostic::DiagnosticExt, err, solution::Solution, text, AocParse, Error, Result};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Ship(pub Vec<Stack>);
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Stack(pub Vec<Crate>);
+
+#[derive(AocParse, PartialEq, Debug)]
+#[aoc(pattern = "move {mv} from {from} to {to}")]
+pub struct Instruction {
+    pub mv: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+pub type Crate = char;
+
+/// Errors point into `s`, so it should be the start of the puzzle input.
+impl FromStr for Ship {
+    type Err = Error;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let mut stacks = Vec::new();
+        for (idx, column) in text::labelled_columns(s)?.into_iter().enumerate() {
+            if column.label.parse() != Ok(idx + 1) {
+                return Err(err!("expected stack {}", idx + 1)).at(s, column.label);
+            }
+            let mut stack = Stack(Vec::new());
+            for token in column.bottom_up() {
+                match *token
+                    .chars()
+                    .filter(|c| c.is_alphabetic())
+                    .collect::<Vec<_>>()
+                    .as_slice()
+                {
+                    [c] => stack.0.push(c),
+                    _ => return Err(err!("could not parse crate: {:?}", token)).at(s, token),
+                }
+            }
+            stacks.push(stack);
+        }
+        Ok(Ship(stacks))
+    }
+}
+
+pub fn part1(ship: &mut Ship, instructions: &[Instruction]) -> Result<String> {
+    for Instruction { mv, to, from } in instructions {
+        // Account for zero indexing
+        let (to, from) = (to - 1, from - 1);
+        for _ in 0..*mv {
+            let Some(crate_) = ship.0[from].0.pop() else {
+                return Err(err!("empty stack!"));
+            };
+            ship.0[to].0.push(crate_);
+        }
+    }
+
+    Ok(ship
+        .0
+        .iter()
+        .map(|stack| *stack.0.last().unwrap())
+        .collect())
+}
+
+pub fn part2(ship: &mut Ship, instructions: &[Instruction]) -> Result<String> {
+    for Instruction { mv, to, from } in instructions {
+        // Account for zero indexing
+        let (to, from) = (to - 1, from - 1);
+        let len = ship.0[from].0.len();
+
+        if ship.0[from].0.get(len - mv..).is_none() {
+            return Err(err!("not enough crates in {} (tried to get {})", from, mv));
+        }
+        let crates: Vec<_> = ship.0[from].0.drain(len - mv..).collect();
+        ship.0[to].0.extend(crates);
+    }
+
+    Ok(ship
+        .0
+        .iter()
+        .map(|stack| *stack.0.last().unwrap())
+        .collect())
+}
+
+pub fn parse_input(input: &str) -> Result<(Ship, Vec<Instruction>)> {
+    let mut splitter = input.split("\n\n");
+    let (Some(ship_str), Some(instructions_str)) = (splitter.by_ref().next(), splitter.next())
+    else {
+        return Err(err!("not enough parts to input"));
+    };
+    Ok((
+        ship_str.parse()?,
+        instructions_str
+            .lines()
+            .map(|line| line.parse().at(input, line))
+            .collect::<Result<Vec<_>>>()?,
+    ))
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 5;
+    type Parsed = (Ship, Vec<Instruction>);
+    type Params = ();
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parse_input(input)
+    }
+
+    // Both parts rearrange the crates, so each starts from its own copy
+    fn part1((ship, instructions): &Self::Parsed, (): &()) -> Result<String> {
+        part1(&mut ship.clone(), instructions)
+    }
+
+    fn part2((ship, instructions): &Self::Parsed, (): &()) -> Result<String> {
+        part2(&mut ship.clone(), instructions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::diagnostic::Diagnostic;
+
+    static EXAMPLE_INPUT: &str = r"
+    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+
+    #[test]
+    fn test_parse_instruction() {
+        let input = "move 1 from 2 to 1";
+        let parsed: Instruction = input.parse().unwrap();
+        let expected = Instruction {
+            mv: 1,
+            from: 2,
+            to: 1,
+        };
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn test_parse_ship() {
+        let input = r"
+    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+";
+
+        let parsed: Ship = input.parse().unwrap();
+        let expected = Ship(vec![
+            Stack(vec!['Z', 'N']),
+            Stack(vec!['M', 'C', 'D']),
+            Stack(vec!['P']),
+        ]);
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_token() {
+        let err = "[N] [CD]\n 1   2\n".parse::<Ship>().unwrap_err();
+        let diagnostic = err.downcast_ref::<Diagnostic>().unwrap();
+        assert_eq!(
+            (diagnostic.line, diagnostic.column, diagnostic.width),
+            (1, 5, 4)
+        );
+
+        let input = EXAMPLE_INPUT.replace("from 1 to 3", "from one to 3");
+        let err = Day05::parse(&input).unwrap_err();
+        let diagnostic = err.downcast_ref::<Diagnostic>().unwrap();
+        assert_eq!(
+            (diagnostic.line, diagnostic.column, diagnostic.width),
+            (8, 13, 3)
+        );
+    }
+
+    aoc::aoc_tests! {
+        Day05;
+        example: EXAMPLE_INPUT => ("CMZ", "MCD"),
+        real: INPUT => (stored, stored),
+    }
+}
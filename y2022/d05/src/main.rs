@@ -0,0 +1,3 @@
+fn main() -> aoc::Result<()> {
+    aoc::solution::run::<y2022_d05::Day05>(y2022_d05::INPUT)
+}
@@ -0,0 +1,93 @@
+#![warn(clippy::pedantic)]
+#![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+use aoc::{
+    bitset::{BitSet, Lower},
+    err,
+    solution::Solution,
+    Result,
+};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+aoc::params! {
+    /// How many distinct characters in a row make each marker.
+    pub struct Windows {
+        packet: usize = 4,
+        message: usize = 14,
+    }
+}
+
+pub fn scan_buffer(buffer: &str, window_size: usize) -> Result<usize> {
+    if window_size == 0 {
+        return Err(err!("A marker needs at least one character"));
+    }
+    for (idx, window) in buffer
+        .trim_end()
+        .as_bytes()
+        .windows(window_size)
+        .enumerate()
+    {
+        let seen = BitSet::<Lower>::try_from_iter(window.iter().copied())?;
+        if seen.len() == window_size {
+            return Ok(idx + window_size);
+        }
+    }
+    Err(err!("No marker found!"))
+}
+
+pub fn part1(buffer: &str, windows: &Windows) -> Result<usize> {
+    scan_buffer(buffer, windows.packet)
+}
+
+pub fn part2(buffer: &str, windows: &Windows) -> Result<usize> {
+    scan_buffer(buffer, windows.message)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 6;
+    type Parsed = String;
+    type Params = Windows;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(buffer: &String, windows: &Windows) -> Result<usize> {
+        part1(buffer, windows)
+    }
+
+    fn part2(buffer: &String, windows: &Windows) -> Result<usize> {
+        part2(buffer, windows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc::aoc_tests! {
+        Day06;
+        example1: "mjqjpqmgbljsphdztnvjfqwrcgsmlb" => (7, 19),
+        example2: "bvwbjplbgvbhsrlpgdmjqwftvncz" => (5, 23),
+        example3: "nppdvjthqldpwncqszvftbrmjlhg" => (6, 23),
+        example4: "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg" => (10, 29),
+        example5: "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw" => (11, 26),
+        swapped: "mjqjpqmgbljsphdztnvjfqwrcgsmlb", packet = 14, message = 4 => (19, 7),
+        real: INPUT => (stored, stored),
+    }
+
+    #[test]
+    fn test_empty_window() {
+        let windows = Windows {
+            packet: 0,
+            ..Windows::default()
+        };
+        assert!(part1("mjqjpqmgbljsphdztnvjfqwrcgsmlb", &windows).is_err());
+        assert!(scan_buffer("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 0).is_err());
+    }
+}
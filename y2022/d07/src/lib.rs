@@ -1,7 +1,15 @@
-#![cfg_attr(feature = "bench", feature(test))]
+#![cfg_attr(all(test, feature = "bench"), feature(test))]
 #![allow(dead_code)]
 #![warn(clippy::pedantic)]
-use aoc::{err, Result};
+#![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+use aoc::{
+    diagnostic::DiagnosticExt,
+    err,
+    math::{checked_add, checked_sub, checked_sum},
+    memo::Memo,
+    solution::Solution,
+    AocParse, Result,
+};
 
 use std::cell::RefCell;
 use std::path::PathBuf;
@@ -9,14 +17,25 @@ use std::rc::{Rc, Weak};
 
 use std::ops::ControlFlow;
 
-const INPUT: &str = include_str!("../input.txt");
+pub const INPUT: &str = include_str!("../input.txt");
+
+aoc::params! {
+    pub struct Sizes {
+        /// The largest directory that counts towards part 1.
+        small: u32 = 100_000,
+        /// The size of the whole filesystem.
+        disk: u32 = 70_000_000,
+        /// How much free space the update needs.
+        free: u32 = 30_000_000,
+    }
+}
 
 #[derive(Debug)]
-struct Arena(Vec<ArenaItem>);
-type ArenaIndex = usize;
+pub struct Arena(Vec<ArenaItem>);
+pub type ArenaIndex = usize;
 
 #[derive(Debug)]
-enum ArenaItem {
+pub enum ArenaItem {
     Dir(ArenaDir),
     File(ArenaFile),
 }
@@ -38,7 +57,7 @@ impl ArenaItem {
 }
 
 #[derive(Debug)]
-struct ArenaDir {
+pub struct ArenaDir {
     parent: Option<ArenaIndex>,
     children: Vec<ArenaIndex>,
     name: String,
@@ -61,7 +80,7 @@ impl From<ArenaDir> for ArenaItem {
 }
 
 #[derive(Debug)]
-struct ArenaFile {
+pub struct ArenaFile {
     parent: Option<ArenaIndex>,
     name: String,
     size: u32,
@@ -97,7 +116,7 @@ impl Arena {
 
             if let ArenaItem::Dir(ref mut dir) = self.0[parent_idx] {
                 dir.children.push(idx);
-            };
+            }
         }
         idx
     }
@@ -133,16 +152,26 @@ impl Arena {
         self.iter_indices().map(|idx| self.at(idx))
     }
 
-    fn size(&self, idx: ArenaIndex) -> u32 {
-        match self.at(idx) {
-            ArenaItem::Dir(d) => d.children.iter().map(|idx| self.size(*idx)).sum(),
-            ArenaItem::File(f) => f.size,
-        }
+    /// The size of the item at `idx`, caching the size of every directory
+    /// under it in `sizes` so each is only added up once; `None` there marks
+    /// one that overflows.
+    fn size(&self, sizes: &mut Memo<ArenaIndex, Option<u32>>, idx: ArenaIndex) -> Result<u32> {
+        sizes
+            .get(idx, &|size, idx| match self.at(idx) {
+                ArenaItem::Dir(d) => d
+                    .children
+                    .iter()
+                    .map(|idx| size.call(*idx))
+                    .collect::<Option<Vec<_>>>()
+                    .and_then(|sizes| checked_sum(sizes).ok()),
+                ArenaItem::File(f) => Some(f.size),
+            })
+            .ok_or_else(|| err!("the size of `{}` overflows u32", self.at(idx).name()))
     }
 }
 
 #[derive(Clone, Debug, Default)]
-struct Dir {
+pub struct Dir {
     name: String,
     parent: Option<Weak<RefCell<Self>>>,
     children: Vec<Rc<RefCell<Item>>>,
@@ -172,7 +201,7 @@ impl Dir {
                 d.borrow_mut().parent = Some(Rc::downgrade(base));
             }
             Item::File(ref f) => f.borrow_mut().parent = Some(Rc::downgrade(base)),
-        };
+        }
         base.borrow_mut().children.push(Rc::clone(item));
     }
 
@@ -219,7 +248,7 @@ impl Dir {
 }
 
 #[derive(Clone, Debug, Default)]
-struct File {
+pub struct File {
     parent: Option<Weak<RefCell<Dir>>>,
     name: String,
     size: u32,
@@ -235,24 +264,30 @@ impl File {
     }
 }
 
-trait Sizeable {
-    fn size(&self) -> u32;
+pub trait Sizeable {
+    /// The total size, or an error if it overflows.
+    fn size(&self) -> Result<u32>;
 }
 
 impl Sizeable for File {
-    fn size(&self) -> u32 {
-        self.size
+    fn size(&self) -> Result<u32> {
+        Ok(self.size)
     }
 }
 
 impl Sizeable for Dir {
-    fn size(&self) -> u32 {
-        self.children.iter().map(|c| c.borrow().size()).sum()
+    fn size(&self) -> Result<u32> {
+        checked_sum(
+            self.children
+                .iter()
+                .map(|c| c.borrow().size())
+                .collect::<Result<Vec<_>>>()?,
+        )
     }
 }
 
 impl Sizeable for Item {
-    fn size(&self) -> u32 {
+    fn size(&self) -> Result<u32> {
         match self {
             Item::File(f) => f.borrow().size(),
             Item::Dir(d) => d.borrow().size(),
@@ -260,14 +295,14 @@ impl Sizeable for Item {
     }
 }
 
-trait Child {
+pub trait Child {
     fn parent(&self) -> Option<Rc<RefCell<Dir>>>;
     fn name(&self) -> String;
     fn abspath(&self) -> Result<PathBuf> {
         let mut v = vec![self.name()];
         let mut parent = self.parent();
         while let Some(ref p) = parent {
-            let name = p.borrow().name().to_string();
+            let name = p.borrow().name().clone();
             v.push(name);
             parent = p.clone().borrow().parent();
         }
@@ -295,7 +330,7 @@ impl Child for Dir {
         self.parent.as_ref()?.upgrade()
     }
     fn name(&self) -> String {
-        self.name.to_string()
+        self.name.clone()
     }
 }
 
@@ -304,103 +339,121 @@ impl Child for File {
         self.parent.as_ref()?.upgrade()
     }
     fn name(&self) -> String {
-        self.name.to_string()
+        self.name.clone()
     }
 }
 
 #[derive(Clone, Debug)]
-enum Item {
+pub enum Item {
     Dir(Rc<RefCell<Dir>>),
     File(Rc<RefCell<File>>),
 }
 
-fn parse_input_arena(input: &str) -> Result<Arena> {
+/// One line of terminal output.
+#[derive(AocParse, Debug, PartialEq)]
+pub enum Line {
+    #[aoc(pattern = "$ cd {0}")]
+    Cd(String),
+    #[aoc(pattern = "$ ls")]
+    Ls,
+    #[aoc(pattern = "dir {0}")]
+    Dir(String),
+    #[aoc(pattern = "{size} {name}")]
+    File { size: u32, name: String },
+}
+
+pub fn parse_input_arena(input: &str) -> Result<Arena> {
     let mut arena = Arena::new();
     let mut cwd: Option<ArenaIndex> = None;
     for line in input.lines() {
-        match line.split_whitespace().collect::<Vec<_>>().as_slice() {
-            ["$", "cd", "/"] => {
-                cwd = Some(arena.add_item(ArenaDir::new("/"), None));
-            }
-            ["$", "cd", dir] => {
-                let Some(ref mut cwd) = cwd else {
-                    return Err(err!("Attempt to cd while uninitialized"));
-                };
-
-                let cwd_dir = arena.dir_at(*cwd)?;
-
-                let target = if *dir == ".." {
-                    cwd_dir.parent.as_ref()
-                } else {
-                    cwd_dir
-                        .children
-                        .iter()
-                        .find(|idx| arena.at(**idx).name() == *dir)
+        let mut step = || -> Result<()> {
+            match line.parse()? {
+                Line::Cd(dir) if dir == "/" => {
+                    cwd = Some(arena.add_item(ArenaDir::new("/"), None));
                 }
-                .ok_or_else(|| err!("unable to find child for cd to {dir}"))?;
+                Line::Cd(dir) => {
+                    let Some(ref mut cwd) = cwd else {
+                        return Err(err!("Attempt to cd while uninitialized"));
+                    };
+
+                    let cwd_dir = arena.dir_at(*cwd)?;
+
+                    let target = if dir == ".." {
+                        cwd_dir.parent.as_ref()
+                    } else {
+                        cwd_dir
+                            .children
+                            .iter()
+                            .find(|idx| arena.at(**idx).name() == dir)
+                    }
+                    .ok_or_else(|| err!("unable to find child for cd to {dir}"))?;
 
-                *cwd = *target;
-            }
-            ["$", "ls"] => (),
-            ["dir", name] => {
-                let Some(cwd) = cwd else {
-                    return Err(err!("Attempt to add a dir with no cwd"));
-                };
-                let dir = ArenaDir::new(name);
-                arena.add_item(dir, Some(cwd));
-            }
-            [num, name] if num.parse::<u32>().is_ok() => {
-                let Some(cwd) = cwd else {
-                    return Err(err!("Attempt to add a file with no cwd"));
-                };
-                let num = num.parse::<u32>().unwrap();
-                let file = ArenaFile::new(name, num);
-                arena.add_item(file, Some(cwd));
+                    *cwd = *target;
+                }
+                Line::Ls => (),
+                Line::Dir(name) => {
+                    let Some(cwd) = cwd else {
+                        return Err(err!("Attempt to add a dir with no cwd"));
+                    };
+                    let dir = ArenaDir::new(name);
+                    arena.add_item(dir, Some(cwd));
+                }
+                Line::File { size, name } => {
+                    let Some(cwd) = cwd else {
+                        return Err(err!("Attempt to add a file with no cwd"));
+                    };
+                    let file = ArenaFile::new(name, size);
+                    arena.add_item(file, Some(cwd));
+                }
             }
-            _ => return Err(err!("Unrecognized input: {}", line)),
-        }
+            Ok(())
+        };
+        step().at(input, line)?;
     }
     Ok(arena)
 }
 
-fn parse_input(input: &str) -> Result<Rc<RefCell<Dir>>> {
+pub fn parse_input(input: &str) -> Result<Rc<RefCell<Dir>>> {
     let mut root = None;
     let mut cwd: Option<Rc<RefCell<Dir>>> = None;
     for line in input.lines() {
-        match line.split_whitespace().collect::<Vec<_>>().as_slice() {
-            ["$", "cd", "/"] => {
-                root = Some(Dir::root());
-                cwd = root.clone();
-            }
-            ["$", "cd", dir] => {
-                let Some(ref mut cwd) = cwd else {
-                    return Err(err!("Attempt to cd while uninitialized"));
-                };
-                Dir::cd(cwd, dir)?;
-            }
-            ["$", "ls"] => (),
-            ["dir", name] => {
-                let Some(ref mut cwd) = cwd else {
-                    return Err(err!("Attempt to add a dir with no cwd"));
-                };
-                let dir = Rc::new(RefCell::new(Item::Dir(Dir::new(name, None))));
-                Dir::add_child(cwd, &dir);
-            }
-            [num, name] if num.parse::<u32>().is_ok() => {
-                let Some(ref mut cwd) = cwd else {
-                    return Err(err!("Attempt to add a file with no cwd"));
-                };
-                let num = num.parse::<u32>().unwrap();
-                let file = Rc::new(RefCell::new(Item::File(File::new(name, num))));
-                Dir::add_child(cwd, &file);
+        let mut step = || -> Result<()> {
+            match line.parse()? {
+                Line::Cd(dir) if dir == "/" => {
+                    root = Some(Dir::root());
+                    cwd.clone_from(&root);
+                }
+                Line::Cd(dir) => {
+                    let Some(ref mut cwd) = cwd else {
+                        return Err(err!("Attempt to cd while uninitialized"));
+                    };
+                    Dir::cd(cwd, dir)?;
+                }
+                Line::Ls => (),
+                Line::Dir(name) => {
+                    let Some(ref mut cwd) = cwd else {
+                        return Err(err!("Attempt to add a dir with no cwd"));
+                    };
+                    let dir = Rc::new(RefCell::new(Item::Dir(Dir::new(name, None))));
+                    Dir::add_child(cwd, &dir);
+                }
+                Line::File { size, name } => {
+                    let Some(ref mut cwd) = cwd else {
+                        return Err(err!("Attempt to add a file with no cwd"));
+                    };
+                    let file = Rc::new(RefCell::new(Item::File(File::new(name, size))));
+                    Dir::add_child(cwd, &file);
+                }
             }
-            _ => return Err(err!("Unrecognized input: {}", line)),
-        }
+            Ok(())
+        };
+        step().at(input, line)?;
     }
     root.ok_or_else(|| err!("input not parsed"))
 }
 
 impl Item {
+    #[allow(clippy::iter_without_into_iter)]
     fn iter(&self) -> ItemIterator {
         ItemIterator {
             stack: vec![Rc::new(RefCell::new(self.clone()))],
@@ -450,7 +503,7 @@ impl Item {
     }
 }
 
-struct ItemIterator {
+pub struct ItemIterator {
     stack: Vec<Rc<RefCell<Item>>>,
 }
 
@@ -468,118 +521,165 @@ impl Iterator for ItemIterator {
     }
 }
 
-fn part1_arena(root: &Arena, size_limit: u32) -> u32 {
-    root.iter_indices()
+pub fn part1_arena(root: &Arena, sizes: &Sizes) -> Result<u32> {
+    let mut dir_sizes = Memo::new();
+    let small = root
+        .iter_indices()
         .filter_map(|idx| {
-            let ArenaItem::Dir(_) = root.at(idx) else { return None };
-            let size = root.size(idx);
-            if size <= size_limit {
-                Some(size)
-            } else {
-                None
+            let ArenaItem::Dir(_) = root.at(idx) else {
+                return None;
+            };
+            match root.size(&mut dir_sizes, idx) {
+                Ok(size) if size > sizes.small => None,
+                size => Some(size),
             }
         })
-        .sum()
+        .collect::<Result<Vec<_>>>()?;
+    checked_sum(small)
 }
 
-fn part1_iter(root: &Item, size_limit: u32) -> u32 {
-    root.iter()
+pub fn part1_iter(root: &Item, sizes: &Sizes) -> Result<u32> {
+    let small = root
+        .iter()
         .filter_map(|i| match &*i.borrow() {
-            i @ Item::Dir(_) if i.size() < size_limit => Some(i.size()),
-            _ => None,
+            i @ Item::Dir(_) => match i.size() {
+                Ok(size) if size >= sizes.small => None,
+                Ok(size) => {
+                    aoc::trace!("small dir", path = traced_path(i), size = size);
+                    Some(Ok(size))
+                }
+                Err(e) => Some(Err(e)),
+            },
+            Item::File(_) => None,
         })
-        .sum::<u32>()
+        .collect::<Result<Vec<_>>>()?;
+    checked_sum(small)
 }
 
-fn part1_internal_iter(root: &Item, size_limit: u32) -> u32 {
+pub fn part1_internal_iter(root: &Item, sizes: &Sizes) -> Result<u32> {
     let mut sum = 0;
-    root.find_map(&mut |item| {
-        let Item::Dir(_) = item else { return None::<()> };
-        let size = item.size();
-        if size <= size_limit {
-            sum += size;
+    let failed = root.find_map(&mut |item| {
+        let Item::Dir(_) = item else {
+            return None;
+        };
+        let mut add = || -> Result<()> {
+            let size = item.size()?;
+            if size <= sizes.small {
+                sum = checked_add(sum, size)?;
+            }
+            Ok(())
         };
-        None::<()>
+        add().err()
     });
-    sum
+    failed.map_or(Ok(sum), Err)
 }
 
-fn part2_iter(root: &Item) -> Result<u32> {
-    let (fs_size, free_min) = (70_000_000, 30_000_000);
-    let used_space = root.size();
+pub fn part2_iter(root: &Item, sizes: &Sizes) -> Result<u32> {
+    let used_space = root.size()?;
 
-    let currently_free = fs_size - used_space;
-    let needed = free_min - currently_free;
+    let currently_free = checked_sub(sizes.disk, used_space)?;
+    let needed = sizes.free.saturating_sub(currently_free);
+    aoc::trace!(
+        "space",
+        used = used_space,
+        free = currently_free,
+        needed = needed
+    );
 
     root.iter()
         .filter_map(|item| match &*item.borrow() {
-            Item::Dir(d) if d.borrow().size() < needed => None,
-            Item::Dir(d) => Some(d.borrow().size()),
+            i @ Item::Dir(d) => match d.borrow().size() {
+                Ok(size) if size < needed => None,
+                Ok(size) => {
+                    aoc::trace!("candidate", path = traced_path(i), size = size);
+                    Some(Ok(size))
+                }
+                Err(e) => Some(Err(e)),
+            },
             Item::File(_) => None,
         })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
         .min()
         .ok_or_else(|| err!("No sufficiently large directory found"))
 }
 
-fn part2_internal_iter(root: &Item) -> Result<u32> {
-    let (fs_size, free_min) = (70_000_000, 30_000_000);
-    let used_space = root.size();
+pub fn part2_internal_iter(root: &Item, sizes: &Sizes) -> Result<u32> {
+    let used_space = root.size()?;
 
-    let currently_free = fs_size - used_space;
-    let needed = free_min - currently_free;
+    let currently_free = checked_sub(sizes.disk, used_space)?;
+    let needed = sizes.free.saturating_sub(currently_free);
 
     let mut result: Option<u32> = None;
-    root.find_map(&mut |item| {
-        match item {
-            Item::Dir(d) if d.borrow().size() < needed => (),
-            Item::Dir(d) => {
-                let size = d.borrow().size();
-                let Some(smallest) = result else {
-                    result = Some(size);
-                    return None::<()>;
-                };
-                if size < smallest {
-                    result = Some(size);
-                };
-            }
-            Item::File(_) => (),
+    let failed = root.find_map(&mut |item| {
+        let Item::Dir(d) = item else {
+            return None;
         };
+        let size = match d.borrow().size() {
+            Ok(size) => size,
+            Err(e) => return Some(e),
+        };
+        if size >= needed && result.is_none_or(|smallest| size < smallest) {
+            result = Some(size);
+        }
         None
     });
+    if let Some(e) = failed {
+        return Err(e);
+    }
     result.ok_or_else(|| err!("No sufficiently large directory found"))
 }
 
-fn part2_arena(arena: &Arena) -> Result<u32> {
-    let (fs_size, free_min) = (70_000_000, 30_000_000);
-    let used_space = arena.size(0);
+pub fn part2_arena(arena: &Arena, sizes: &Sizes) -> Result<u32> {
+    let mut dir_sizes = Memo::new();
+    let used_space = arena.size(&mut dir_sizes, 0)?;
 
-    let currently_free = fs_size - used_space;
-    let needed = free_min - currently_free;
+    let currently_free = checked_sub(sizes.disk, used_space)?;
+    let needed = sizes.free.saturating_sub(currently_free);
 
     arena
         .iter_indices()
         .filter_map(|idx| match arena.at(idx) {
-            ArenaItem::Dir(_) if arena.size(idx) < needed => None,
-            ArenaItem::Dir(_) => Some(arena.size(idx)),
+            ArenaItem::Dir(_) => match arena.size(&mut dir_sizes, idx) {
+                Ok(size) if size < needed => None,
+                size => Some(size),
+            },
             ArenaItem::File(_) => None,
         })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
         .min()
         .ok_or_else(|| err!("No sufficiently large directory found"))
 }
 
-fn main() -> Result<()> {
-    let parsed = Item::Dir(parse_input(INPUT)?);
+/// An item's path for [`aoc::trace!`] events.
+fn traced_path(item: &Item) -> String {
+    item.abspath()
+        .map(|path| path.display().to_string())
+        .unwrap_or_default()
+}
+
+pub struct Day07;
 
-    println!("day 07 part 1: {}", part1_iter(&parsed, 100_000));
-    println!("day 07 part 2: {}", part2_iter(&parsed)?);
+impl Solution for Day07 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 7;
+    type Parsed = Item;
+    type Params = Sizes;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    // println!("day 07 part 1: {}", part1_internal_iter(&parsed, 100_000));
-    // println!("day 07 part 2: {}", part2_internal_iter(&parsed)?);
+    fn parse(input: &str) -> Result<Item> {
+        Ok(Item::Dir(parse_input(input)?))
+    }
+
+    fn part1(root: &Item, sizes: &Sizes) -> Result<u32> {
+        part1_iter(root, sizes)
+    }
 
-    // let arena = parse_input_arena(include_str!("../input.txt"))?;
-    // println!("day 07 part 1: {}", part1_arena(&arena, 100_000));
-    // println!("day 07 part 2: {}", part2_arena(&arena)?);
-    Ok(())
+    fn part2(root: &Item, sizes: &Sizes) -> Result<u32> {
+        part2_iter(root, sizes)
+    }
 }
 
 #[cfg(test)]
@@ -665,7 +765,7 @@ $ ls
         let binding = root.borrow();
         let Item::Dir(ref d) = *binding.children[1].borrow() else {
             panic!("second child should be a dir")
-    };
+        };
         assert_eq!(d.borrow().abspath().unwrap(), PathBuf::from("/bar"));
     }
 
@@ -682,23 +782,23 @@ $ ls
         let child_dir = Rc::new(RefCell::new(Item::Dir(child_dir)));
         Dir::add_child(&root, &child_dir);
 
-        assert_eq!(Item::Dir(root).size(), 21);
-        assert_eq!(child_dir.borrow().size(), 14);
-        assert_eq!(child_file.borrow().size(), 7);
+        assert_eq!(Item::Dir(root).size().unwrap(), 21);
+        assert_eq!(child_dir.borrow().size().unwrap(), 14);
+        assert_eq!(child_file.borrow().size().unwrap(), 7);
 
         let root = parse_input(EXAMPLE_INPUT).unwrap();
 
         // Clone to prevent dropping the root
         let cwd = &mut root.clone();
         // root, a, d, e
-        assert_eq!(cwd.borrow().size(), 48_381_165);
+        assert_eq!(cwd.borrow().size().unwrap(), 48_381_165);
         Dir::cd(cwd, "d").unwrap();
-        assert_eq!(cwd.borrow().size(), 24_933_642);
+        assert_eq!(cwd.borrow().size().unwrap(), 24_933_642);
         Dir::cd(cwd, "..").unwrap();
         Dir::cd(cwd, "a").unwrap();
-        assert_eq!(cwd.borrow().size(), 94853);
+        assert_eq!(cwd.borrow().size().unwrap(), 94853);
         Dir::cd(cwd, "e").unwrap();
-        assert_eq!(cwd.borrow().size(), 584);
+        assert_eq!(cwd.borrow().size().unwrap(), 584);
     }
 
     const ITER_RESULT: [&str; 14] = [
@@ -823,13 +923,31 @@ $ ls
     #[test]
     fn test_part1_iter() {
         let root = Item::Dir(parse_input(EXAMPLE_INPUT).unwrap());
-        assert_eq!(part1_iter(&root, 100_000), 95437);
+        assert_eq!(part1_iter(&root, &Sizes::default()).unwrap(), 95437);
     }
 
     #[test]
     fn test_part1_internal_iter() {
         let root = Item::Dir(parse_input(EXAMPLE_INPUT).unwrap());
-        assert_eq!(part1_internal_iter(&root, 100_000), 95437);
+        assert_eq!(
+            part1_internal_iter(&root, &Sizes::default()).unwrap(),
+            95437
+        );
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!("$ cd ..".parse::<Line>().unwrap(), Line::Cd("..".into()));
+        assert_eq!("$ ls".parse::<Line>().unwrap(), Line::Ls);
+        assert_eq!("dir e".parse::<Line>().unwrap(), Line::Dir("e".into()));
+        assert_eq!(
+            "584 i".parse::<Line>().unwrap(),
+            Line::File {
+                size: 584,
+                name: "i".into()
+            }
+        );
+        assert!("$ rm -rf /".parse::<Line>().is_err());
     }
 
     #[test]
@@ -855,18 +973,26 @@ $ ls
     #[test]
     fn test_part1_arena() {
         let parsed = parse_input_arena(EXAMPLE_INPUT).unwrap();
-        assert_eq!(part1_arena(&parsed, 100_000), 95437);
+        assert_eq!(part1_arena(&parsed, &Sizes::default()).unwrap(), 95437);
     }
 
     #[test]
     fn test_part1_all_implementations() {
-        let my_part1_solution = 1_517_599;
         let parsed = Item::Dir(parse_input(INPUT).unwrap());
-        assert_eq!(part1_iter(&parsed, 100_000), my_part1_solution);
-        assert_eq!(part1_internal_iter(&parsed, 100_000), my_part1_solution);
+        let answer = part1_iter(&parsed, &Sizes::default()).unwrap();
         assert_eq!(
-            part1_arena(&parse_input_arena(INPUT).unwrap(), 100_000),
-            my_part1_solution
+            part1_internal_iter(&parsed, &Sizes::default()).unwrap(),
+            answer
+        );
+        assert_eq!(
+            part1_arena(&parse_input_arena(INPUT).unwrap(), &Sizes::default()).unwrap(),
+            answer
+        );
+        aoc::store::assert_stored(
+            env!("CARGO_MANIFEST_DIR"),
+            aoc::store::Puzzle::new(2022, 7).unwrap(),
+            aoc::solution::Stage::Part1,
+            &answer.to_string(),
         );
     }
 
@@ -874,92 +1000,114 @@ $ ls
     fn test_part2_iter() {
         let solution = 24_933_642;
         let parsed = Item::Dir(parse_input(EXAMPLE_INPUT).unwrap());
-        assert_eq!(part2_iter(&parsed).unwrap(), solution);
+        assert_eq!(part2_iter(&parsed, &Sizes::default()).unwrap(), solution);
     }
 
     #[test]
     fn test_part2_internal_iter() {
         let solution = 24_933_642;
         let parsed = Item::Dir(parse_input(EXAMPLE_INPUT).unwrap());
-        assert_eq!(part2_internal_iter(&parsed).unwrap(), solution);
+        assert_eq!(
+            part2_internal_iter(&parsed, &Sizes::default()).unwrap(),
+            solution
+        );
     }
 
     #[test]
     fn test_part2_arena() {
         let solution = 24_933_642;
         let arena = parse_input_arena(EXAMPLE_INPUT).unwrap();
-        assert_eq!(part2_arena(&arena).unwrap(), solution);
+        assert_eq!(part2_arena(&arena, &Sizes::default()).unwrap(), solution);
+    }
+
+    #[test]
+    fn test_size_overflow() {
+        let input = "$ cd /\n$ ls\n3000000000 a\n3000000000 b\n";
+        let root = Item::Dir(parse_input(input).unwrap());
+        let arena = parse_input_arena(input).unwrap();
+        let sizes = Sizes::default();
+        assert!(root.size().is_err());
+        assert!(part1_iter(&root, &sizes).is_err());
+        assert!(part1_internal_iter(&root, &sizes).is_err());
+        assert!(part1_arena(&arena, &sizes).is_err());
+        assert!(part2_iter(&root, &sizes).is_err());
+        assert!(part2_internal_iter(&root, &sizes).is_err());
+        assert!(part2_arena(&arena, &sizes).is_err());
+    }
+
+    aoc::aoc_tests! {
+        Day07;
+        example: EXAMPLE_INPUT => (95437, 24_933_642),
+        tiny_dirs: EXAMPLE_INPUT, small = 1_000 => (584, 24_933_642),
+        real: INPUT => (stored, stored),
     }
 }
 
-#[cfg(feature = "bench")]
+#[cfg(all(test, feature = "bench"))]
 mod benches {
     extern crate test;
     use super::*;
     use test::Bencher;
 
-    const PART1_SOLUTION: u32 = 1_517_599;
-    const PART2_SOLUTION: u32 = 2_481_982;
-
     #[bench]
     fn bench_parse(b: &mut Bencher) {
         b.iter(|| {
             let _parsed = Item::Dir(parse_input(INPUT).unwrap());
-        })
+        });
     }
 
     #[bench]
     fn bench_part1_iter(b: &mut Bencher) {
         let parsed = Item::Dir(parse_input(INPUT).unwrap());
         b.iter(|| {
-            assert_eq!(part1_iter(&parsed, 100_000), PART1_SOLUTION);
-        })
+            test::black_box(part1_iter(&parsed, &Sizes::default()).unwrap());
+        });
     }
 
     #[bench]
     fn bench_part1_internal_iter(b: &mut Bencher) {
         let parsed = Item::Dir(parse_input(INPUT).unwrap());
         b.iter(|| {
-            assert_eq!(part1_internal_iter(&parsed, 100_000), PART1_SOLUTION);
-        })
+            test::black_box(part1_internal_iter(&parsed, &Sizes::default()).unwrap());
+        });
     }
 
     #[bench]
     fn bench_parse_arena(b: &mut Bencher) {
         b.iter(|| {
             let _arena = parse_input_arena(INPUT).unwrap();
-        })
+        });
     }
 
     #[bench]
     fn bench_part1_arena(b: &mut Bencher) {
         let arena = parse_input_arena(INPUT).unwrap();
         b.iter(|| {
-            assert_eq!(part1_arena(&arena, 100_000), PART1_SOLUTION);
-        })
+            test::black_box(part1_arena(&arena, &Sizes::default()).unwrap());
+        });
     }
 
     #[bench]
     fn bench_part2_iter(b: &mut Bencher) {
         let parsed = Item::Dir(parse_input(INPUT).unwrap());
         b.iter(|| {
-            assert_eq!(part2_iter(&parsed).unwrap(), PART2_SOLUTION);
-        })
+            test::black_box(part2_iter(&parsed, &Sizes::default()).unwrap());
+        });
     }
 
     #[bench]
     fn bench_part2_internal_iter(b: &mut Bencher) {
         let parsed = Item::Dir(parse_input(INPUT).unwrap());
         b.iter(|| {
-            assert_eq!(part2_internal_iter(&parsed).unwrap(), PART2_SOLUTION);
-        })
+            test::black_box(part2_internal_iter(&parsed, &Sizes::default()).unwrap());
+        });
     }
 
     #[bench]
     fn bench_part2_arena(b: &mut Bencher) {
         let arena = parse_input_arena(INPUT).unwrap();
         b.iter(|| {
-            assert_eq!(part2_arena(&arena).unwrap(), PART2_SOLUTION);
-        })
+            test::black_box(part2_arena(&arena, &Sizes::default()).unwrap());
+        });
     }
 }
@@ -1,9 +1,10 @@
 #![warn(clippy::pedantic)]
+#![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
 
-use aoc::{err, Error, Result};
+use aoc::{err, solution::Solution, Error, Result};
 use std::str::FromStr;
 
-const INPUT: &str = include_str!("../input.txt");
+pub const INPUT: &str = include_str!("../input.txt");
 
 impl Forest {
     fn as_grid(&self) -> impl Iterator<Item = ((usize, usize), &Tree)> {
@@ -17,11 +18,11 @@ impl Forest {
         let y_dim = self.0.len();
         if y_dim == 0 {
             return Err(err!("empty forest"));
-        };
+        }
         let x_dim = self.0[0].len();
         if x_dim == 0 {
             return Err(err!("empty forest"));
-        };
+        }
         Ok((x_dim, y_dim))
     }
 
@@ -110,7 +111,7 @@ impl Forest {
     }
 }
 
-fn part1(forest: &Forest) -> Result<u32> {
+pub fn part1(forest: &Forest) -> Result<u32> {
     let (x_dim, y_dim) = forest.dims()?;
 
     Ok(forest.as_grid().fold(0, |acc, ((x, y), tree)| {
@@ -135,11 +136,11 @@ macro_rules! get_score {
             $(
                 $obj.views.$dir.ok_or_else(|| $crate::err!("missing score"))
             ),*
-        ].into_iter().collect::<$crate::Result<Vec<_>>>().map(|r| r.into_iter().map(|v| v as u32).product())
+        ].into_iter().collect::<$crate::Result<Vec<_>>>().and_then(|r| r.into_iter().map(|v| Ok(u32::try_from(v)?)).product::<$crate::Result<u32>>())
     }}
 }
 
-fn part2(forest: &mut Forest) -> Result<u32> {
+pub fn part2(forest: &mut Forest) -> Result<u32> {
     forest.get_distances()?;
     forest
         .as_grid()
@@ -150,20 +151,20 @@ fn part2(forest: &mut Forest) -> Result<u32> {
         .ok_or_else(|| err!("no max found"))
 }
 
-type Height = u8;
+pub type Height = u8;
 
-#[derive(Debug, Default, PartialEq, PartialOrd)]
-struct Views {
-    left: Option<usize>,
-    right: Option<usize>,
-    up: Option<usize>,
-    down: Option<usize>,
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Views {
+    pub left: Option<usize>,
+    pub right: Option<usize>,
+    pub up: Option<usize>,
+    pub down: Option<usize>,
 }
 
-#[derive(Debug, Default, PartialEq, PartialOrd)]
-struct Tree {
-    height: Height,
-    views: Views,
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Tree {
+    pub height: Height,
+    pub views: Views,
 }
 
 impl Tree {
@@ -200,27 +201,44 @@ impl FromStr for Forest {
     }
 }
 
-#[derive(Debug)]
-struct Forest(Vec<Vec<Tree>>);
+#[derive(Clone, Debug)]
+pub struct Forest(pub Vec<Vec<Tree>>);
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 8;
+    type Parsed = Forest;
+    type Params = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Forest> {
+        input.parse()
+    }
+
+    fn part1(forest: &Forest, (): &()) -> Result<u32> {
+        part1(forest)
+    }
 
-fn main() -> Result<()> {
-    let mut forest: Forest = INPUT.parse()?;
-    println!("day 08 part 1: {}", part1(&forest)?);
-    println!("day 08 part 2: {}", part2(&mut forest)?);
-    Ok(())
+    // Part 2 fills in each tree's views, so it works on a copy
+    fn part2(forest: &Forest, (): &()) -> Result<u32> {
+        part2(&mut forest.clone())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    static EXAMPLE_INPUT: &str = r#"
+    static EXAMPLE_INPUT: &str = r"
 30373
 25512
 65332
 33549
 35390
-"#;
+";
 
     #[test]
     fn test_parse() {
@@ -231,11 +249,6 @@ mod tests {
         assert_eq!(parsed.0[4][4].height, 0);
     }
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(&EXAMPLE_INPUT.parse().unwrap()).unwrap(), 21);
-    }
-
     #[test]
     fn test_get_tree_mut() {
         let mut parsed: Forest = EXAMPLE_INPUT.parse().unwrap();
@@ -258,15 +271,11 @@ mod tests {
         assert_eq!(views.left.unwrap(), 2);
         assert_eq!(views.down.unwrap(), 1);
         assert_eq!(views.right.unwrap(), 2);
-
-        forest = EXAMPLE_INPUT.parse().unwrap();
-        assert_eq!(part2(&mut forest).unwrap(), 8);
     }
 
-    #[test]
-    fn test_regression() {
-        let mut forest: Forest = INPUT.parse().unwrap();
-        assert_eq!(part1(&forest).unwrap(), 1809);
-        assert_eq!(part2(&mut forest).unwrap(), 479400);
+    aoc::aoc_tests! {
+        Day08;
+        example: EXAMPLE_INPUT => (21, 8),
+        real: INPUT => (stored, stored),
     }
 }
@@ -0,0 +1,50 @@
+// #![warn(clippy::pedantic)]
+// #![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+use aoc::{parse_input, solution::Solution, Result};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub fn part1(_input: &[String]) -> Result<u32> {
+    todo!()
+}
+
+pub fn part2(_input: &[String]) -> Result<u32> {
+    todo!()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 9;
+    type Parsed = Vec<String>;
+    type Params = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(parse_input!(input)?)
+    }
+
+    fn part1(input: &Self::Parsed, (): &()) -> Result<u32> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed, (): &()) -> Result<u32> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE_INPUT: &str = "\
+";
+
+    aoc::aoc_tests! {
+        Day09;
+        example: EXAMPLE_INPUT => (_, _),
+        real: INPUT => (_, _),
+    }
+}